@@ -0,0 +1,96 @@
+use aoc2024::solutions::{Solution, SOLUTIONS};
+use criterion::{BenchmarkId, Criterion};
+use serde_json::Value;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{env, fs};
+
+/// The inputs to benchmark a day against: the embedded example and, when present, the real input.
+fn inputs(solution: &Solution) -> Vec<(&'static str, String)> {
+    let mut inputs = vec![("example", solution.example.to_string())];
+    let real = format!("input/2024/day{}.txt", solution.day);
+    if let Ok(input) = fs::read_to_string(real) {
+        inputs.push(("real", input));
+    }
+    inputs
+}
+
+fn bench_solutions(c: &mut Criterion) {
+    SOLUTIONS.iter().for_each(|solution| {
+        let mut group = c.benchmark_group(format!("day{:02}", solution.day));
+
+        inputs(solution).iter().for_each(|(name, input)| {
+            if *name == "real" {
+                // some real inputs take seconds per iteration
+                group.sample_size(10);
+            }
+
+            // some days generate their example differently from real input
+            let prepare = |input: &str| match *name {
+                "example" => solution.prepare_example(),
+                _ => solution.prepare(input),
+            };
+
+            let id = BenchmarkId::new(format!("part{}-generator", solution.part), name);
+            group.bench_with_input(id, input, |b, input| {
+                b.iter(|| prepare(black_box(input)).unwrap())
+            });
+
+            let solver = prepare(input).unwrap();
+            let id = BenchmarkId::new(format!("part{}-solver", solution.part), name);
+            group.bench_function(id, |b| b.iter(&solver));
+        });
+
+        group.finish();
+    });
+}
+
+fn criterion_dir() -> PathBuf {
+    let target = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| String::from("target"));
+    Path::new(&target).join("criterion")
+}
+
+/// The mean time of the most recent run of a benchmark, read from criterion's saved estimates.
+fn mean(group: &str, function: &str, input: &str) -> Option<Duration> {
+    let path = criterion_dir()
+        .join(group)
+        .join(function)
+        .join(input)
+        .join("new/estimates.json");
+    let estimates: Value = serde_json::from_str(&fs::read_to_string(path).ok()?).ok()?;
+    let nanos = estimates["mean"]["point_estimate"].as_f64()?;
+    Some(Duration::from_nanos(nanos as u64))
+}
+
+/// Prints a markdown table of mean times that can be pasted next to another commit's for comparison.
+fn print_summary() {
+    println!("\n| day | part | input | generator | solver |");
+    println!("|----:|-----:|:------|----------:|-------:|");
+
+    SOLUTIONS.iter().for_each(|solution| {
+        let group = format!("day{:02}", solution.day);
+        ["example", "real"].iter().for_each(|input| {
+            let generator = mean(&group, &format!("part{}-generator", solution.part), input);
+            let solver = mean(&group, &format!("part{}-solver", solution.part), input);
+            if let (Some(generator), Some(solver)) = (generator, solver) {
+                println!(
+                    "| {} | {} | {input} | {generator:.1?} | {solver:.1?} |",
+                    solution.day, solution.part
+                );
+            }
+        });
+    });
+}
+
+fn main() {
+    let mut criterion = Criterion::default()
+        .warm_up_time(Duration::from_secs(1))
+        .measurement_time(Duration::from_secs(2))
+        .configure_from_args();
+
+    bench_solutions(&mut criterion);
+    criterion.final_summary();
+
+    print_summary();
+}
@@ -0,0 +1,139 @@
+use serde::Serialize;
+
+/// Heap usage over a measured section of code.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Number of allocations, counting each reallocation as one.
+    pub allocations: u64,
+    /// Total bytes requested by those allocations.
+    pub bytes: u64,
+    /// Most bytes live at once, above what was live when the section started.
+    pub peak: u64,
+    /// Bytes still live when the section finished, above what was live when it started.
+    pub retained: u64,
+}
+
+/// Runs `f`, returning its heap usage when built with the `alloc-stats` feature.
+///
+/// The counters are process-wide, so allocations made by other threads while `f` runs are
+/// included.
+pub fn measure<T>(f: impl FnOnce() -> T) -> (T, Option<AllocStats>) {
+    #[cfg(feature = "alloc-stats")]
+    {
+        let (value, stats) = counting::measure(f);
+        (value, Some(stats))
+    }
+
+    #[cfg(not(feature = "alloc-stats"))]
+    {
+        (f(), None)
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use super::AllocStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+    static BYTES: AtomicU64 = AtomicU64::new(0);
+    static CURRENT: AtomicU64 = AtomicU64::new(0);
+    static PEAK: AtomicU64 = AtomicU64::new(0);
+
+    /// The system allocator, counting every allocation and tracking the live byte high-water mark.
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn grow(size: u64) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: u64) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                grow(layout.size() as u64);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                grow(layout.size() as u64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            shrink(layout.size() as u64);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                shrink(layout.size() as u64);
+                grow(new_size as u64);
+            }
+            new_ptr
+        }
+    }
+
+    pub(super) fn measure<T>(f: impl FnOnce() -> T) -> (T, AllocStats) {
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+        let bytes = BYTES.load(Ordering::Relaxed);
+        let start = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(start, Ordering::Relaxed);
+
+        let value = f();
+
+        let stats = AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(start),
+            retained: CURRENT.load(Ordering::Relaxed).saturating_sub(start),
+        };
+        (value, stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn test_measure() {
+        let (v, stats) = measure(|| {
+            let scratch: Vec<u64> = vec![0; 1024];
+            let kept: Vec<u8> = Vec::with_capacity(100);
+            drop(scratch);
+            kept
+        });
+        let stats = stats.unwrap();
+
+        // other test threads share the counters, so only the monotonic ones are exact lower bounds
+        assert_eq!(v.capacity(), 100);
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 8 * 1024 + 100);
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn test_measure_disabled() {
+        let (v, stats) = measure(|| vec![1, 2, 3]);
+        assert_eq!(v, vec![1, 2, 3]);
+        assert_eq!(stats, None);
+    }
+}
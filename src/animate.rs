@@ -0,0 +1,358 @@
+#[cfg(feature = "day06")]
+use crate::day_06;
+#[cfg(feature = "day09")]
+use crate::day_09;
+#[cfg(feature = "day14")]
+use crate::day_14;
+use crate::error::ParseError;
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// A simulation that advances a step at a time and can be drawn as text between steps.
+///
+/// Every iterator that can display its current state is one: the items are ignored, `next` is
+/// only called for its effect on the state.
+pub trait Steps: Display {
+    /// Advances one step, returning whether there was one to take.
+    fn step(&mut self) -> bool;
+}
+
+impl<I: Iterator + Display> Steps for I {
+    fn step(&mut self) -> bool {
+        self.next().is_some()
+    }
+}
+
+/// Draws a simulation frame by frame, either to a terminal at a fixed rate or as plain text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Animator {
+    /// The time between frames, or `None` to write every frame as plain text with no delay.
+    frame_time: Option<Duration>,
+    every: usize,
+}
+
+// clear the screen and hide the cursor, move to the top left, clear below the cursor, and show
+// the cursor again
+const START: &str = "\x1b[2J\x1b[?25l";
+const HOME: &str = "\x1b[H";
+const CLEAR_BELOW: &str = "\x1b[J";
+const END: &str = "\x1b[?25h";
+
+impl Animator {
+    /// Redraws each frame in place with ANSI cursor control, `fps` times a second.
+    pub fn new(fps: f64) -> Self {
+        Animator {
+            frame_time: Some(Duration::from_secs_f64(1.0 / fps)),
+            every: 1,
+        }
+    }
+
+    /// Writes each frame after the last under a `frame <n>` header, as fast as possible.
+    pub fn headless() -> Self {
+        Animator {
+            frame_time: None,
+            every: 1,
+        }
+    }
+
+    /// Only draws every `n`th step, for simulations with too many to watch. The first and last
+    /// states are always drawn.
+    pub fn every(mut self, n: usize) -> Self {
+        self.every = n.max(1);
+        self
+    }
+
+    /// Runs `steps` to the end, drawing its state before the first step and after every
+    /// `every`th one to `out`. Returns the number of steps taken.
+    pub fn play(&self, steps: &mut dyn Steps, out: &mut impl Write) -> io::Result<usize> {
+        if self.frame_time.is_some() {
+            write!(out, "{START}")?;
+        }
+
+        let mut next_frame = Instant::now();
+        let mut count = 0;
+        let mut drawn = None;
+        loop {
+            if drawn != Some(count) && count % self.every == 0 {
+                self.draw(steps, count, &mut next_frame, out)?;
+                drawn = Some(count);
+            }
+            if !steps.step() {
+                break;
+            }
+            count += 1;
+        }
+        if drawn != Some(count) {
+            self.draw(steps, count, &mut next_frame, out)?;
+        }
+
+        if self.frame_time.is_some() {
+            write!(out, "{END}")?;
+        }
+        out.flush()?;
+        Ok(count)
+    }
+
+    fn draw(
+        &self,
+        steps: &dyn Steps,
+        step: usize,
+        next_frame: &mut Instant,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        let Some(frame_time) = self.frame_time else {
+            return writeln!(out, "frame {step}\n{steps}");
+        };
+
+        thread::sleep(next_frame.saturating_duration_since(Instant::now()));
+        write!(out, "{HOME}{steps}step {step}\n{CLEAR_BELOW}")?;
+        out.flush()?;
+        *next_frame = Instant::now().max(*next_frame) + frame_time;
+        Ok(())
+    }
+}
+
+/// The days and parts with a simulation to animate.
+pub const SIMULATIONS: &[(u8, u8)] = &[
+    #[cfg(feature = "day06")]
+    (6, 1),
+    #[cfg(feature = "day09")]
+    (9, 1),
+    #[cfg(feature = "day09")]
+    (9, 2),
+    #[cfg(feature = "day14")]
+    (14, 1),
+    #[cfg(feature = "day14")]
+    (14, 2),
+];
+
+/// Parses `input` for `day` and plays `part`'s simulation with `animator`, or `None` if there is
+/// no simulation for it.
+#[cfg_attr(
+    not(any(feature = "day06", feature = "day09", feature = "day14")),
+    allow(unused_variables)
+)]
+pub fn animate(
+    day: u8,
+    part: u8,
+    input: &str,
+    animator: &Animator,
+    out: &mut impl Write,
+) -> Option<Result<usize, AnimateError>> {
+    match (day, part) {
+        #[cfg(feature = "day06")]
+        (6, 1) => Some(
+            day_06::input_generator(input)
+                .map_err(AnimateError::Parse)
+                .and_then(|map| Ok(animator.play(&mut day_06::Patrol::new(&map), out)?)),
+        ),
+        #[cfg(feature = "day09")]
+        (9, 1) => Some(
+            day_09::input_generator(input)
+                .map_err(AnimateError::Parse)
+                .and_then(|disk| Ok(animator.play(&mut day_09::Defrag::new(disk), out)?)),
+        ),
+        #[cfg(feature = "day09")]
+        (9, 2) => Some(
+            day_09::input_generator(input)
+                .map_err(AnimateError::Parse)
+                .and_then(|disk| Ok(animator.play(&mut day_09::MoveFiles::new(disk), out)?)),
+        ),
+        #[cfg(feature = "day14")]
+        (14, _) => play_lobby(day_14::input_generator(input), part, animator, out),
+        _ => None,
+    }
+}
+
+/// Like `animate`, on the day's example from its puzzle description.
+pub fn animate_example(
+    day: u8,
+    part: u8,
+    animator: &Animator,
+    out: &mut impl Write,
+) -> Option<Result<usize, AnimateError>> {
+    match day {
+        // the example's robots are in a smaller lobby than the real input's
+        #[cfg(feature = "day14")]
+        14 => play_lobby(
+            day_14::example_generator(day_14::EXAMPLE),
+            part,
+            animator,
+            out,
+        ),
+        _ => {
+            let example = crate::solutions::find(day, None).first()?.example;
+            animate(day, part, example, animator, out)
+        }
+    }
+}
+
+/// Plays day 14's robots in `lobby` for `part`.
+#[cfg(feature = "day14")]
+fn play_lobby(
+    lobby: Result<day_14::Lobby, ParseError>,
+    part: u8,
+    animator: &Animator,
+    out: &mut impl Write,
+) -> Option<Result<usize, AnimateError>> {
+    let motion: fn(&day_14::Lobby) -> day_14::Motion = match part {
+        1 => |lobby| day_14::Motion::part1(lobby),
+        2 => |lobby| day_14::Motion::part2(lobby),
+        _ => return None,
+    };
+    Some(
+        lobby
+            .map_err(AnimateError::Parse)
+            .and_then(|lobby| Ok(animator.play(&mut motion(&lobby), out)?)),
+    )
+}
+
+/// Why a simulation couldn't be played.
+#[derive(Debug)]
+pub enum AnimateError {
+    Parse(ParseError),
+    Io(io::Error),
+}
+
+impl From<io::Error> for AnimateError {
+    fn from(e: io::Error) -> Self {
+        AnimateError::Io(e)
+    }
+}
+
+impl Display for AnimateError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            AnimateError::Parse(e) => write!(f, "{e}"),
+            AnimateError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for AnimateError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    /// Counts down from a number, drawn as that many stars.
+    struct Countdown(usize);
+
+    impl Iterator for Countdown {
+        type Item = ();
+
+        fn next(&mut self) -> Option<()> {
+            self.0 = self.0.checked_sub(1)?;
+            Some(())
+        }
+    }
+
+    impl Display for Countdown {
+        fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+            writeln!(f, "{}", "*".repeat(self.0))
+        }
+    }
+
+    fn play(animator: Animator, from: usize) -> String {
+        let mut out = vec![];
+        let steps = animator.play(&mut Countdown(from), &mut out).unwrap();
+        assert_eq!(steps, from);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_headless() {
+        let exp = indoc! {"
+            frame 0
+            ***
+
+            frame 1
+            **
+
+            frame 2
+            *
+
+            frame 3
+
+
+        "};
+        assert_eq!(play(Animator::headless(), 3), exp);
+    }
+
+    #[test]
+    fn test_every() {
+        let frames = play(Animator::headless().every(2), 5);
+        let headers: Vec<&str> = frames.lines().filter(|l| l.starts_with("frame")).collect();
+        assert_eq!(headers, ["frame 0", "frame 2", "frame 4", "frame 5"]);
+
+        let frames = play(Animator::headless().every(2), 4);
+        let headers: Vec<&str> = frames.lines().filter(|l| l.starts_with("frame")).collect();
+        assert_eq!(headers, ["frame 0", "frame 2", "frame 4"]);
+    }
+
+    #[test]
+    fn test_terminal() {
+        let start = Instant::now();
+        let frames = play(Animator::new(100.0), 2);
+        assert!(start.elapsed() >= Duration::from_millis(20));
+        assert_eq!(
+            frames,
+            "\x1b[2J\x1b[?25l\
+             \x1b[H**\nstep 0\n\x1b[J\
+             \x1b[H*\nstep 1\n\x1b[J\
+             \x1b[H\nstep 2\n\x1b[J\
+             \x1b[?25h"
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "day06", feature = "day09"))]
+    fn test_animate() {
+        let mut out = vec![];
+        let steps = animate(9, 2, "12345\n", &Animator::headless(), &mut out);
+        assert_eq!(steps.unwrap().unwrap(), 0);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "frame 0\n0..111....22222\n\n"
+        );
+
+        let mut out = vec![];
+        let steps = animate(9, 1, "12345", &Animator::headless(), &mut out);
+        assert_eq!(steps.unwrap().unwrap(), 5);
+        assert!(String::from_utf8(out)
+            .unwrap()
+            .ends_with("frame 5\n022111222......\n\n"));
+
+        let mut out = vec![];
+        let err = animate(6, 1, "..\n.#", &Animator::headless(), &mut out).unwrap();
+        assert!(matches!(err, Err(AnimateError::Parse(_))));
+        assert!(animate(6, 2, "^", &Animator::headless(), &mut out).is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "day14")]
+    fn test_animate_example() {
+        // the example's robots move in their own 11 by 7 lobby
+        let mut out = vec![];
+        let animator = Animator::headless().every(100);
+        let steps = animate_example(14, 1, &animator, &mut out);
+        assert_eq!(steps.unwrap().unwrap(), 100);
+        assert!(String::from_utf8(out).unwrap().ends_with(indoc! {"
+            frame 100
+            ......2..1.
+            ...........
+            1..........
+            .11........
+            .....1.....
+            ...12......
+            .1....1....
+
+        "}));
+
+        assert!(animate_example(14, 3, &animator, &mut vec![]).is_none());
+    }
+}
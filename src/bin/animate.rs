@@ -0,0 +1,163 @@
+use aoc2024::animate::{self, Animator, SIMULATIONS};
+use aoc2024::input::{self, parse_arg, Flags};
+use std::process::ExitCode;
+use std::{env, io};
+
+const USAGE: &str =
+    "usage: animate <day> [--part <n>] [--input <path>|-|--example] [--fps <n>] [--every <n>] [--headless]
+
+Plays <day>'s simulation in the terminal, redrawing it --fps times a second (default 10). Without
+--input it reads input/2024/day<day>.txt, and --part defaults to 1. --example plays the example
+from the puzzle description instead. --every <n> only draws every nth step. --headless writes
+every frame one after the other to stdout instead, as plain text.";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u8,
+    part: u8,
+    input: Option<String>,
+    example: bool,
+    fps: f64,
+    every: usize,
+    headless: bool,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut day = None;
+    let mut parsed = Args {
+        day: 0,
+        part: 1,
+        input: None,
+        example: false,
+        fps: 10.0,
+        every: 1,
+        headless: false,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" | "-p" => parsed.part = args.parse("--part", "part")?,
+            "--input" | "-i" => parsed.input = Some(args.value("--input")?),
+            "--example" | "-e" => parsed.example = true,
+            "--fps" => {
+                let v = args.value("--fps")?;
+                parsed.fps = v
+                    .parse()
+                    .ok()
+                    .filter(|fps: &f64| fps.is_finite() && *fps > 0.0)
+                    .ok_or_else(|| format!("invalid fps {v:?}"))?;
+            }
+            "--every" => {
+                let v = args.value("--every")?;
+                parsed.every = v
+                    .parse()
+                    .ok()
+                    .filter(|every| *every > 0)
+                    .ok_or_else(|| format!("invalid step count {v:?}"))?;
+            }
+            "--headless" => parsed.headless = true,
+            _ if day.is_none() => day = Some(parse_arg(&arg, "day")?),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    parsed.day = day.ok_or("missing day")?;
+    if parsed.example && parsed.input.is_some() {
+        return Err(String::from("--example and --input can't be used together"));
+    }
+    Ok(parsed)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let animator = match args.headless {
+        true => Animator::headless(),
+        false => Animator::new(args.fps),
+    }
+    .every(args.every);
+    let out = &mut io::stdout().lock();
+
+    let (path, played) = match args.example {
+        true => (
+            String::from("example"),
+            animate::animate_example(args.day, args.part, &animator, out),
+        ),
+        false => {
+            let path = args
+                .input
+                .unwrap_or_else(|| format!("input/2024/day{}.txt", args.day));
+            let input = input::read(&path).map_err(|e| format!("{path}: {e}"))?;
+            let played = animate::animate(args.day, args.part, &input, &animator, out);
+            (path, played)
+        }
+    };
+
+    played
+        .ok_or_else(|| format!("no simulation for day {} part {}", args.day, args.part))?
+        .map(|_| ())
+        .map_err(|e| format!("{path}: {e}"))
+}
+
+fn usage() -> String {
+    let simulations: Vec<String> = SIMULATIONS
+        .iter()
+        .map(|(day, part)| format!("day {day} part {part}"))
+        .collect();
+    format!("{USAGE}\n\nSimulations: {}", simulations.join(", "))
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("9 -p 2 --input - --fps 2.5 --every 10 --headless").unwrap(),
+            Args {
+                day: 9,
+                part: 2,
+                input: Some(String::from("-")),
+                example: false,
+                fps: 2.5,
+                every: 10,
+                headless: true,
+            }
+        );
+
+        let parsed = args("6").unwrap();
+        assert_eq!((parsed.part, parsed.fps, parsed.every), (1, 10.0, 1));
+        assert!(!parsed.headless);
+        assert!(args("14 --example").unwrap().example);
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("").unwrap_err(), "missing day");
+        assert_eq!(args("6 --fps 0").unwrap_err(), "invalid fps \"0\"");
+        assert_eq!(args("6 --every 0").unwrap_err(), "invalid step count \"0\"");
+        assert_eq!(
+            args("14 --example --input -").unwrap_err(),
+            "--example and --input can't be used together"
+        );
+    }
+}
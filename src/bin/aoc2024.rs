@@ -0,0 +1,314 @@
+use aoc2024::input::{self, Flags};
+use aoc2024::report::{PartReport, Report};
+use aoc2024::solutions::{self, Solution};
+use std::io::{BufRead, BufReader};
+use std::process::ExitCode;
+use std::time::Duration;
+use std::{env, fs, io};
+
+const USAGE: &str =
+    "usage: aoc2024 [--day <day>] [--part <1|2>] [--input <path>|-|--example] [--format <text|json|csv>] [--stream]
+
+Runs the registered solutions and prints each answer with its generator and solver timings.
+Without --day every compiled-in day is run, see the list below; each day is a `dayNN` cargo
+feature, all on by default. Without --input each day reads input/2024/day<day>.txt,
+and days whose input file is missing are skipped. --input - reads from stdin.
+--example runs each day on the example from its puzzle description instead.
+--format json or csv prints a machine-readable report instead of text.
+--stream reads the input a buffer at a time for each part, for days with a reader-based
+generator, instead of loading all of it first. Streaming stdin needs --part.";
+
+#[derive(Default, Debug, PartialEq)]
+enum Format {
+    #[default]
+    Text,
+    Json,
+    Csv,
+}
+
+#[derive(Default, Debug)]
+struct Args {
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    example: bool,
+    format: Format,
+    stream: bool,
+    help: bool,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut parsed = Args::default();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" | "-d" => parsed.day = Some(args.parse("--day", "day")?),
+            "--part" | "-p" => {
+                let v = args.value("--part")?;
+                match v.as_str() {
+                    "1" | "2" => parsed.part = v.parse().ok(),
+                    _ => return Err(format!("invalid part {v:?}")),
+                }
+            }
+            "--input" | "-i" => parsed.input = Some(args.value("--input")?),
+            "--example" | "-e" => parsed.example = true,
+            "--format" | "-f" => {
+                let v = args.value("--format")?;
+                parsed.format = match v.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(format!("invalid format {v:?}")),
+                }
+            }
+            "--stream" => parsed.stream = true,
+            "--help" | "-h" => parsed.help = true,
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    if parsed.input.is_some() && parsed.day.is_none() {
+        return Err(String::from("--input requires --day"));
+    }
+    if parsed.example && (parsed.input.is_some() || parsed.stream) {
+        return Err(String::from(
+            "--example can't be used with --input or --stream",
+        ));
+    }
+    if parsed.stream && parsed.input.as_deref() == Some("-") && parsed.part.is_none() {
+        return Err(String::from("streaming stdin requires --part"));
+    }
+
+    Ok(parsed)
+}
+
+fn open_input(path: &str) -> io::Result<Box<dyn BufRead>> {
+    if path == "-" {
+        Ok(Box::new(io::stdin().lock()))
+    } else {
+        Ok(Box::new(BufReader::new(fs::File::open(path)?)))
+    }
+}
+
+/// Runs each of `parts` on its own reader over `path`, so that no part holds all of the input.
+fn run_streamed(parts: &[Solution], path: &str) -> Result<Vec<PartReport>, String> {
+    parts
+        .iter()
+        .map(|solution| {
+            let mut reader = open_input(path).map_err(|e| format!("{path}: {e}"))?;
+            Ok(PartReport::run_reader(solution, &mut reader))
+        })
+        .collect()
+}
+
+fn run(args: &Args) -> Result<Report, String> {
+    let days = match args.day {
+        Some(day) => vec![day],
+        None => solutions::days(),
+    };
+    let mut report = Report::default();
+
+    for day in days {
+        if !(1..=25).contains(&day) {
+            return Err(format!("no such day {day}, the days run from 1 to 25"));
+        }
+        let parts = solutions::find(day, args.part);
+        if parts.is_empty() && !solutions::compiled(day) {
+            return Err(format!(
+                "day {day} is not compiled in, build with --features day{day:02}"
+            ));
+        }
+        if parts.is_empty() {
+            return Err(format!("no solution registered for day {day}"));
+        }
+
+        if args.example {
+            parts
+                .iter()
+                .for_each(|solution| report.push(PartReport::run_example(solution)));
+            continue;
+        }
+
+        if args.stream {
+            let path = args
+                .input
+                .clone()
+                .unwrap_or_else(|| format!("input/2024/day{day}.txt"));
+            match run_streamed(&parts, &path) {
+                Ok(parts) => parts.into_iter().for_each(|p| report.push(p)),
+                Err(e) if args.day.is_none() => eprintln!("Day {day} - skipped, {e}"),
+                Err(e) => return Err(e),
+            }
+            continue;
+        }
+
+        let input = match &args.input {
+            Some(path) => input::read(path).map_err(|e| format!("{path}: {e}"))?,
+            None => {
+                let path = format!("input/2024/day{day}.txt");
+                match fs::read_to_string(&path) {
+                    Ok(input) => input,
+                    Err(e) if args.day.is_none() => {
+                        eprintln!("Day {day} - skipped, {path}: {e}");
+                        continue;
+                    }
+                    Err(e) => return Err(format!("{path}: {e}")),
+                }
+            }
+        };
+
+        parts
+            .iter()
+            .for_each(|solution| report.push(PartReport::run(solution, &input)));
+    }
+
+    Ok(report)
+}
+
+fn allocations(allocs: Option<u64>, bytes: Option<u64>, peak: Option<u64>) -> String {
+    match (allocs, bytes, peak) {
+        (Some(allocs), Some(bytes), Some(peak)) => {
+            format!(" ({allocs} allocs, {bytes} bytes, peak {peak} bytes)")
+        }
+        _ => String::new(),
+    }
+}
+
+fn print_text(report: &Report) {
+    report
+        .parts
+        .iter()
+        .for_each(|p| match (&p.answer, &p.error) {
+            (Some(answer), _) => println!(
+                "Day {} - Part {} : {}\n\tgenerator: {:?}{},\n\trunner: {:?}{}\n",
+                p.day,
+                p.part,
+                answer,
+                Duration::from_nanos(p.generator_ns),
+                allocations(
+                    p.generator_allocs,
+                    p.generator_alloc_bytes,
+                    p.generator_peak_bytes
+                ),
+                Duration::from_nanos(p.solver_ns),
+                allocations(p.solver_allocs, p.solver_alloc_bytes, p.solver_peak_bytes),
+            ),
+            (None, error) => eprintln!(
+                "Day {} - Part {} : error: {}\n",
+                p.day,
+                p.part,
+                error.as_deref().unwrap_or_default()
+            ),
+        });
+}
+
+fn print_report(report: &Report, format: &Format) -> Result<(), String> {
+    match format {
+        Format::Text => print_text(report),
+        Format::Json => println!("{}", report.to_json().map_err(|e| e.to_string())?),
+        Format::Csv => print!("{}", report.to_csv().map_err(|e| e.to_string())?),
+    }
+    Ok(())
+}
+
+fn usage() -> String {
+    let days: Vec<String> = solutions::days().iter().map(u8::to_string).collect();
+    match days.is_empty() {
+        true => format!("{USAGE}\n\nNo days are compiled in."),
+        false => format!("{USAGE}\n\nCompiled-in days: {}", days.join(", ")),
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage());
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if args.help {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    match run(&args).and_then(|report| {
+        print_report(&report, &args.format)?;
+        Ok(report)
+    }) {
+        Ok(report) if report.has_errors() => ExitCode::FAILURE,
+        Ok(_) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = args("--day 9 --part 2 --input -").unwrap();
+        assert_eq!(parsed.day, Some(9));
+        assert_eq!(parsed.part, Some(2));
+        assert_eq!(parsed.input.as_deref(), Some("-"));
+        assert_eq!(parsed.format, Format::Text);
+
+        let parsed = args("--format csv").unwrap();
+        assert_eq!(parsed.format, Format::Csv);
+        assert!(!parsed.stream);
+
+        let parsed = args("--day 3 --part 1 --input - --stream").unwrap();
+        assert!(parsed.stream);
+        assert!(!parsed.example);
+
+        let parsed = args("--day 14 --example").unwrap();
+        assert!(parsed.example);
+
+        let parsed = args("").unwrap();
+        assert_eq!(parsed.day, None);
+        assert_eq!(parsed.part, None);
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("--part 3").unwrap_err(), "invalid part \"3\"");
+        assert_eq!(args("--day").unwrap_err(), "missing value for --day");
+        assert_eq!(args("--input x").unwrap_err(), "--input requires --day");
+        assert_eq!(args("7").unwrap_err(), "unexpected argument \"7\"");
+        assert_eq!(args("-f xml").unwrap_err(), "invalid format \"xml\"");
+        assert_eq!(
+            args("--day 3 --input - --stream").unwrap_err(),
+            "streaming stdin requires --part"
+        );
+        assert_eq!(
+            args("--day 14 --example --input -").unwrap_err(),
+            "--example can't be used with --input or --stream"
+        );
+    }
+
+    #[test]
+    fn test_run_no_such_day() {
+        for day in [0, 26, 30] {
+            let err = run(&args(&format!("--day {day}")).unwrap()).unwrap_err();
+            assert_eq!(err, format!("no such day {day}, the days run from 1 to 25"));
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "day14")]
+    fn test_run_example() {
+        // day 14's example is in its own smaller lobby
+        let report = run(&args("--day 14 --part 1 --example").unwrap()).unwrap();
+        assert_eq!(report.parts[0].answer.as_deref(), Some("12"));
+    }
+}
@@ -0,0 +1,143 @@
+use aoc2024::client::{Client, Verdict};
+use aoc2024::solutions;
+use std::env;
+use std::process::ExitCode;
+
+const YEAR: u16 = 2024;
+
+const USAGE: &str = "usage: aoc_client fetch <day>
+       aoc_client submit <day> <1|2> [answer]
+
+fetch downloads a day's input to input/2024/day<day>.txt unless it is already there.
+submit posts an answer, running the registered solution on the input when none is given.
+The session cookie is read from the AOC_SESSION environment variable.";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Fetch {
+        day: u8,
+    },
+    Submit {
+        day: u8,
+        part: u8,
+        answer: Option<String>,
+    },
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let args: Vec<String> = args.collect();
+    let day = |i: usize| -> Result<u8, String> {
+        let v = args.get(i).ok_or("missing day")?;
+        v.parse()
+            .ok()
+            .filter(|d| (1..=25).contains(d))
+            .ok_or_else(|| format!("invalid day {v:?}"))
+    };
+
+    match args.first().map(String::as_str) {
+        Some("fetch") if args.len() == 2 => Ok(Command::Fetch { day: day(1)? }),
+        Some("submit") if (3..=4).contains(&args.len()) => {
+            let part = match args[2].as_str() {
+                "1" => 1,
+                "2" => 2,
+                v => return Err(format!("invalid part {v:?}")),
+            };
+            Ok(Command::Submit {
+                day: day(1)?,
+                part,
+                answer: args.get(3).cloned(),
+            })
+        }
+        Some(command) => Err(format!("unexpected arguments to {command:?}")),
+        None => Err(String::from("missing command")),
+    }
+}
+
+fn run(command: Command) -> Result<(), String> {
+    let session = env::var("AOC_SESSION").map_err(|_| "AOC_SESSION is not set")?;
+    let client = Client::new(session.trim());
+
+    match command {
+        Command::Fetch { day } => {
+            client.input(YEAR, day).map_err(|e| e.to_string())?;
+            println!("{}", client.input_path(YEAR, day).display());
+        }
+        Command::Submit { day, part, answer } => {
+            let answer = match answer {
+                Some(answer) => answer,
+                None => {
+                    let solution = *solutions::find(day, Some(part))
+                        .first()
+                        .ok_or_else(|| format!("no solution registered for day {day}"))?;
+                    let input = client.input(YEAR, day).map_err(|e| e.to_string())?;
+                    solution.run(&input).map_err(|e| e.to_string())?.answer
+                }
+            };
+
+            let verdict = client
+                .submit(YEAR, day, part, &answer)
+                .map_err(|e| e.to_string())?;
+            println!("Day {day} - Part {part} : {answer} is {verdict}");
+            if verdict != Verdict::Correct {
+                return Err(String::from("answer not accepted"));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Command, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(args("fetch 9").unwrap(), Command::Fetch { day: 9 });
+        assert_eq!(
+            args("submit 9 2").unwrap(),
+            Command::Submit {
+                day: 9,
+                part: 2,
+                answer: None
+            }
+        );
+        assert_eq!(
+            args("submit 9 1 1928").unwrap(),
+            Command::Submit {
+                day: 9,
+                part: 1,
+                answer: Some(String::from("1928"))
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("").unwrap_err(), "missing command");
+        assert_eq!(args("fetch 26").unwrap_err(), "invalid day \"26\"");
+        assert_eq!(args("submit 9 3").unwrap_err(), "invalid part \"3\"");
+        assert_eq!(
+            args("fetch 1 2").unwrap_err(),
+            "unexpected arguments to \"fetch\""
+        );
+    }
+}
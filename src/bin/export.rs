@@ -0,0 +1,134 @@
+use aoc2024::export::{self, DAYS};
+use aoc2024::input::{self, parse_arg, Flags};
+use std::process::ExitCode;
+use std::{env, fs};
+
+const USAGE: &str = "usage: export <day> [--input <path>|-] [--format dot|svg] [--highlight <n>] [--output <path>|-]
+
+Writes the graph in <day>'s input as Graphviz DOT (the default) or SVG, to --output or stdout.
+Without --input it reads input/2024/day<day>.txt. --highlight picks out part of the graph:";
+
+#[derive(Debug, PartialEq)]
+enum Format {
+    Dot,
+    Svg,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u8,
+    input: Option<String>,
+    format: Format,
+    highlight: Option<usize>,
+    output: String,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut day = None;
+    let mut parsed = Args {
+        day: 0,
+        input: None,
+        format: Format::Dot,
+        highlight: None,
+        output: String::from("-"),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => parsed.input = Some(args.value("--input")?),
+            "--format" | "-f" => {
+                parsed.format = match args.value("--format")?.as_str() {
+                    "dot" => Format::Dot,
+                    "svg" => Format::Svg,
+                    other => return Err(format!("unknown format {other:?}")),
+                }
+            }
+            "--highlight" => parsed.highlight = Some(args.parse("--highlight", "index")?),
+            "--output" | "-o" => parsed.output = args.value("--output")?,
+            _ if day.is_none() => day = Some(parse_arg(&arg, "day")?),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    parsed.day = day.ok_or("missing day")?;
+    Ok(parsed)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let path = args
+        .input
+        .unwrap_or_else(|| format!("input/2024/day{}.txt", args.day));
+    let input = input::read(&path).map_err(|e| format!("{path}: {e}"))?;
+
+    let graph = export::export(args.day, &input, args.highlight)
+        .ok_or_else(|| format!("day {} has no graph to export", args.day))?
+        .map_err(|e| format!("{path}: {e}"))?;
+    let text = match args.format {
+        Format::Dot => graph.to_dot(),
+        Format::Svg => graph.to_svg(),
+    };
+
+    match args.output.as_str() {
+        "-" => print!("{text}"),
+        path => fs::write(path, text).map_err(|e| format!("{path}: {e}"))?,
+    }
+    Ok(())
+}
+
+fn usage() -> String {
+    let days: Vec<String> = DAYS
+        .iter()
+        .map(|(day, highlight)| format!("  day {day:>2}: {highlight}"))
+        .collect();
+    format!("{USAGE}\n{}", days.join("\n"))
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("5 -i - --format svg --highlight 3 -o rules.svg").unwrap(),
+            Args {
+                day: 5,
+                input: Some(String::from("-")),
+                format: Format::Svg,
+                highlight: Some(3),
+                output: String::from("rules.svg"),
+            }
+        );
+
+        let parsed = args("10").unwrap();
+        assert_eq!((parsed.format, parsed.highlight), (Format::Dot, None));
+        assert_eq!(parsed.output, "-");
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("").unwrap_err(), "missing day");
+        assert_eq!(args("5 -f png").unwrap_err(), "unknown format \"png\"");
+        assert_eq!(args("5 --highlight x").unwrap_err(), "invalid index \"x\"");
+    }
+}
@@ -0,0 +1,117 @@
+use aoc2024::gen::{self, GENERATORS};
+use aoc2024::input::{parse_arg, Flags};
+use std::process::ExitCode;
+use std::{env, fs};
+
+const USAGE: &str = "usage: gen_input <day> [--seed <n>] [--size <n>] [--output <path>|-]
+
+Writes a synthetic input for <day>, the same for the same seed and size, to --output or stdout.
+The size defaults to about that of a real input; what it counts depends on the day:";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u8,
+    seed: u64,
+    size: Option<usize>,
+    output: String,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut day = None;
+    let mut parsed = Args {
+        day: 0,
+        seed: 0,
+        size: None,
+        output: String::from("-"),
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--seed" | "-s" => parsed.seed = args.parse("--seed", "seed")?,
+            "--size" | "-n" => {
+                let v = args.value("--size")?;
+                parsed.size = Some(
+                    v.parse()
+                        .ok()
+                        .filter(|size| *size > 0)
+                        .ok_or_else(|| format!("invalid size {v:?}"))?,
+                );
+            }
+            "--output" | "-o" => parsed.output = args.value("--output")?,
+            _ if day.is_none() => day = Some(parse_arg(&arg, "day")?),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    parsed.day = day.ok_or("missing day")?;
+    Ok(parsed)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let generator =
+        gen::find(args.day).ok_or_else(|| format!("no generator for day {}", args.day))?;
+    let input = generator.generate(args.seed, args.size.unwrap_or(generator.default_size));
+
+    match args.output.as_str() {
+        "-" => print!("{input}"),
+        path => fs::write(path, input).map_err(|e| format!("{path}: {e}"))?,
+    }
+    Ok(())
+}
+
+fn usage() -> String {
+    let sizes: Vec<String> = GENERATORS
+        .iter()
+        .map(|g| format!("  day {:>2}: {} ({})", g.day, g.size_unit, g.default_size))
+        .collect();
+    format!("{USAGE}\n{}", sizes.join("\n"))
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("6 --seed 42 --size 500 -o day6.txt").unwrap(),
+            Args {
+                day: 6,
+                seed: 42,
+                size: Some(500),
+                output: String::from("day6.txt"),
+            }
+        );
+
+        let parsed = args("9").unwrap();
+        assert_eq!((parsed.seed, parsed.size), (0, None));
+        assert_eq!(parsed.output, "-");
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("").unwrap_err(), "missing day");
+        assert_eq!(args("6 --size 0").unwrap_err(), "invalid size \"0\"");
+        assert_eq!(args("6 7").unwrap_err(), "unexpected argument \"7\"");
+    }
+}
@@ -0,0 +1,520 @@
+use aoc2024::input::{self, Flags};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::{env, fs};
+
+const USAGE: &str =
+    "usage: new_day <day> [--example <path>|-] [--part1 <answer>] [--part2 <answer>]
+
+Writes src/day_<NN>.rs with a generator, part1/part2 stubs and a test module, declares the
+module in src/lib.rs and registers both parts in src/solutions.rs, both behind the day's
+`dayNN` cargo feature. The example input is embedded as EXAMPLE; tests that need an example
+or an expected answer that wasn't given are emitted as ignored.";
+
+const TEMPLATE: &str = r#"use crate::error::ParseError;
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+__IMPORTS__
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = __EXAMPLE__;
+
+#[aoc_generator(day__DAY__)]
+pub fn input_generator(input: &str) -> Result<Vec<String>, ParseError> {
+    let input = &normalize(input);
+    Ok(input.lines().map(String::from).collect())
+}
+
+#[aoc(day__DAY__, part1)]
+pub fn part1(input: &[String]) -> usize {
+    input.len()
+}
+
+#[aoc(day__DAY__, part2)]
+pub fn part2(input: &[String]) -> usize {
+    input.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+
+    #[test]
+__IGNORE_EXAMPLE__    fn test_input_generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert!(!input.is_empty());
+    }
+
+    #[test]
+__IGNORE_EXAMPLE__    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(input, input_generator(EXAMPLE).unwrap());
+    }
+
+__PART1_TEST__
+__PART2_TEST__}
+"#;
+
+#[derive(Default, Debug)]
+struct Args {
+    day: u8,
+    example: Option<String>,
+    part1: Option<String>,
+    part2: Option<String>,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut parsed = Args::default();
+    let mut day = None;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--example" | "-e" => parsed.example = Some(args.value("--example")?),
+            "--part1" => parsed.part1 = Some(args.value("--part1")?),
+            "--part2" => parsed.part2 = Some(args.value("--part2")?),
+            _ if day.is_none() => {
+                day = Some(
+                    arg.parse()
+                        .ok()
+                        .filter(|d| (1..=25).contains(d))
+                        .ok_or_else(|| format!("invalid day {arg:?}"))?,
+                )
+            }
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    parsed.day = day.ok_or("missing day")?;
+    Ok(parsed)
+}
+
+/// The example as a Rust expression, an `indoc!` block when it spans several lines.
+fn example_literal(example: &str) -> String {
+    let escaped = example
+        .trim_end_matches('\n')
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"");
+
+    if escaped.contains('\n') {
+        let lines: Vec<String> = escaped
+            .lines()
+            .map(|l| {
+                if l.is_empty() {
+                    String::new()
+                } else {
+                    format!("    {l}")
+                }
+            })
+            .collect();
+        format!("indoc! {{\"\n{}\n\"}}", lines.join("\n"))
+    } else {
+        format!("\"{escaped}\"")
+    }
+}
+
+/// The attribute ignoring a test for `reason`.
+fn ignore(reason: &str) -> String {
+    format!("    #[ignore = \"{reason}\"]\n")
+}
+
+/// A test running `part` on the example, ignored when the example or the answer is missing.
+fn part_test(part: u8, answer: Option<&str>, has_example: bool) -> String {
+    let (ignore, expected) = match answer {
+        _ if !has_example => (ignore(NO_EXAMPLE), answer.unwrap_or("0").to_string()),
+        Some(answer) => (String::new(), answer.to_string()),
+        None => (ignore("expected answer not known yet"), String::from("0")),
+    };
+    let name = if part == 1 { "one" } else { "two" };
+
+    format!(
+        "    #[test]
+{ignore}    fn test_part_{name}() {{
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part{part}(&input);
+        assert_eq!(result, {expected});
+    }}
+"
+    )
+}
+
+const NO_EXAMPLE: &str = "example not known yet";
+
+fn render(args: &Args, example: &str) -> String {
+    let literal = example_literal(example);
+    let imports = if literal.starts_with("indoc!") {
+        "use indoc::indoc;\n"
+    } else {
+        ""
+    };
+    let has_example = !example.trim().is_empty();
+    let ignore_example = if has_example {
+        String::new()
+    } else {
+        ignore(NO_EXAMPLE)
+    };
+
+    TEMPLATE
+        .replace("__IMPORTS__", imports)
+        .replace("__EXAMPLE__", &literal)
+        .replace("__DAY__", &args.day.to_string())
+        .replace("__IGNORE_EXAMPLE__", &ignore_example)
+        .replace(
+            "__PART1_TEST__",
+            &part_test(1, args.part1.as_deref(), has_example),
+        )
+        .replace(
+            "__PART2_TEST__",
+            &part_test(2, args.part2.as_deref(), has_example),
+        )
+}
+
+/// The cargo feature that compiles `day` in.
+fn feature(day: u8) -> String {
+    format!("#[cfg(feature = \"day{day:02}\")]")
+}
+
+/// Adds `mod <module>;` behind its day feature to lib.rs, keeping the day modules in order.
+fn register_module(lib: &str, day: u8, module: &str) -> Result<String, String> {
+    let decl = format!("mod {module};");
+    if lib.lines().any(|l| l == decl) {
+        return Err(format!("{module} is already declared in lib.rs"));
+    }
+
+    let mut lines: Vec<&str> = lib.lines().collect();
+    let last_day = lines
+        .iter()
+        .rposition(|l| l.starts_with("mod day_") && *l < decl.as_str())
+        .ok_or("no day modules found in lib.rs")?;
+    let cfg = feature(day);
+    lines.insert(last_day + 1, &cfg);
+    lines.insert(last_day + 2, &decl);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// The day of a `SOLUTIONS` entry line like `solution!(12, 1, ...)`.
+fn entry_day(line: &str) -> Option<u8> {
+    let args = line.trim_start().strip_prefix("solution!(")?;
+    args.split(',').next()?.parse().ok()
+}
+
+/// Adds both parts of `day` to the `SOLUTIONS` table, after the entries of the days before it.
+fn register_solutions(solutions: &str, day: u8, module: &str) -> Result<String, String> {
+    let mut lines: Vec<&str> = solutions.lines().collect();
+    let table = lines
+        .iter()
+        .position(|l| l.starts_with("pub const SOLUTIONS"))
+        .ok_or("no SOLUTIONS table found in solutions.rs")?;
+    let end = table
+        + lines[table..]
+            .iter()
+            .position(|l| *l == "];")
+            .ok_or("unterminated SOLUTIONS table in solutions.rs")?;
+    let last_day = table
+        + lines[table..end]
+            .iter()
+            .rposition(|l| entry_day(l).is_some_and(|d| d < day))
+            .unwrap_or(0);
+
+    let cfg = feature(day);
+    let entries = format!(
+        "    {cfg}\
+         \n    solution!({day}, 1, {module}, input_generator, part1),\
+         \n    {cfg}\
+         \n    solution!({day}, 2, {module}, input_generator, part2),"
+    );
+    lines.insert(last_day + 1, &entries);
+
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Writes the day's module into `src` and registers it in `src`'s lib.rs and solutions.rs,
+/// returning the module's path.
+fn scaffold(src: &Path, args: &Args, example: &str) -> Result<PathBuf, String> {
+    let module = format!("day_{:02}", args.day);
+    let day_path = src.join(format!("{module}.rs"));
+    if day_path.exists() {
+        return Err(format!("{} already exists", day_path.display()));
+    }
+
+    let lib_path = src.join("lib.rs");
+    let lib = fs::read_to_string(&lib_path).map_err(|e| e.to_string())?;
+    let lib = register_module(&lib, args.day, &module)?;
+
+    let solutions_path = src.join("solutions.rs");
+    let solutions = fs::read_to_string(&solutions_path).map_err(|e| e.to_string())?;
+    let solutions = register_solutions(&solutions, args.day, &module)?;
+
+    fs::write(&day_path, render(args, example)).map_err(|e| e.to_string())?;
+    fs::write(&lib_path, lib).map_err(|e| e.to_string())?;
+    fs::write(&solutions_path, solutions).map_err(|e| e.to_string())?;
+    Ok(day_path)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let example = match &args.example {
+        Some(path) => input::read(path).map_err(|e| format!("{path}: {e}"))?,
+        None => String::new(),
+    };
+
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let day_path = scaffold(&src, &args, &example)?;
+    println!("created {}", day_path.display());
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{USAGE}");
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    #[test]
+    fn test_parse_args() {
+        let args = parse_args(
+            "12 --example ex.txt --part1 140"
+                .split_whitespace()
+                .map(String::from),
+        )
+        .unwrap();
+        assert_eq!(args.day, 12);
+        assert_eq!(args.example.as_deref(), Some("ex.txt"));
+        assert_eq!(args.part1.as_deref(), Some("140"));
+        assert_eq!(args.part2, None);
+
+        let err = parse_args(["26"].into_iter().map(String::from)).unwrap_err();
+        assert_eq!(err, "invalid day \"26\"");
+    }
+
+    #[test]
+    fn test_example_literal() {
+        assert_eq!(example_literal("125 17\n"), "\"125 17\"");
+        assert_eq!(
+            example_literal("AAAA\n\n\"B\"\n"),
+            "indoc! {\"\n    AAAA\n\n    \\\"B\\\"\n\"}"
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let args = Args {
+            day: 12,
+            part1: Some(String::from("140")),
+            ..Default::default()
+        };
+        let source = render(&args, "AAAA\nBBCD\n");
+
+        assert!(source.contains("use indoc::indoc;\n"));
+        assert!(source.contains("#[aoc_generator(day12)]"));
+        assert!(source.contains("#[aoc(day12, part2)]"));
+        assert!(source.contains("pub const EXAMPLE: &str = indoc! {\"\n    AAAA\n    BBCD\n\"};"));
+        assert!(source.contains("        assert_eq!(result, 140);"));
+        assert!(source
+            .contains("    #[ignore = \"expected answer not known yet\"]\n    fn test_part_two()"));
+    }
+
+    #[test]
+    fn test_render_without_example() {
+        let args = Args {
+            day: 17,
+            part1: Some(String::from("140")),
+            ..Default::default()
+        };
+        let source = render(&args, "");
+
+        assert!(source.contains("pub const EXAMPLE: &str = \"\";"));
+        // every test that runs on the example is ignored, even with an answer to compare against
+        for test in [
+            "test_input_generator",
+            "test_input_generator_crlf",
+            "test_part_one",
+            "test_part_two",
+        ] {
+            assert!(
+                source.contains(&format!(
+                    "    #[ignore = \"example not known yet\"]\n    fn {test}()"
+                )),
+                "{test} is not ignored"
+            );
+        }
+    }
+
+    /// The days with no module written yet.
+    fn unwritten_days() -> Vec<u8> {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        (1..=25u8)
+            .filter(|d| !root.join(format!("src/day_{d:02}.rs")).exists())
+            .collect()
+    }
+
+    /// A scratch directory named after `test` holding copies of the real lib.rs and
+    /// solutions.rs, so that a change to either that the scaffold misses shows up in the tests.
+    fn scratch_src(test: &str) -> PathBuf {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let src = env::temp_dir().join(format!("aoc2024-{test}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&src);
+        fs::create_dir_all(&src).unwrap();
+        for file in ["lib.rs", "solutions.rs"] {
+            fs::copy(root.join("src").join(file), src.join(file)).unwrap();
+        }
+        src
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let Some(&day) = unwritten_days().first() else {
+            return;
+        };
+        let src = scratch_src("new-day");
+
+        let args = Args {
+            day,
+            ..Default::default()
+        };
+        let day_path = scaffold(&src, &args, "1 2\n3 4\n").unwrap();
+        let read = |file: &str| fs::read_to_string(src.join(file)).unwrap();
+        let source = fs::read_to_string(day_path).unwrap();
+        let (lib, solutions) = (read("lib.rs"), read("solutions.rs"));
+        fs::remove_dir_all(&src).unwrap();
+
+        for item in [
+            "pub const EXAMPLE",
+            "pub fn input_generator",
+            "pub fn part1",
+            "pub fn part2",
+        ] {
+            assert!(source.contains(item), "generated module has no {item}");
+        }
+
+        let (feature, module) = (format!("day{day:02}"), format!("day_{day:02}"));
+        let cfg = format!("#[cfg(feature = \"{feature}\")]");
+        assert!(lib.contains(&format!("{cfg}\nmod {module};\n")));
+
+        let table = &solutions[solutions.find("pub const SOLUTIONS").unwrap()..];
+        let table = &table[..table.find("\n];").unwrap()];
+        for part in 1..=2 {
+            let entry = format!(
+                "    {cfg}\n    solution!({day}, {part}, {module}, input_generator, part{part}),"
+            );
+            assert!(table.contains(&entry), "no registered {entry}");
+        }
+        // the places a new day needs that are already there for all 25
+        assert!(solutions.contains(&format!("cfg!(feature = \"{feature}\")")));
+        let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(manifest.contains(&format!("\n{feature} = []")));
+        assert!(manifest.contains(&format!("\"{feature}\",")));
+    }
+
+    #[test]
+    fn test_scaffold_out_of_order() {
+        let [first, second, ..] = unwritten_days()[..] else {
+            return;
+        };
+        let src = scratch_src("new-day-out-of-order");
+        for day in [second, first] {
+            let args = Args {
+                day,
+                ..Default::default()
+            };
+            scaffold(&src, &args, "").unwrap();
+        }
+        let read = |file: &str| fs::read_to_string(src.join(file)).unwrap();
+        let (lib, solutions) = (read("lib.rs"), read("solutions.rs"));
+        fs::remove_dir_all(&src).unwrap();
+
+        // the same order `solutions::tests::test_days` checks
+        let mut days: Vec<u8> = solutions.lines().filter_map(entry_day).collect();
+        days.dedup();
+        assert!(
+            days.windows(2).all(|w| w[0] < w[1]),
+            "{days:?} out of order"
+        );
+        assert!(days.contains(&first) && days.contains(&second));
+
+        let modules: Vec<&str> = lib.lines().filter(|l| l.starts_with("mod day_")).collect();
+        assert!(modules.is_sorted(), "{modules:?} out of order");
+    }
+
+    #[test]
+    fn test_register_module() {
+        let lib = indoc! {r#"
+            #[cfg(feature = "day01")]
+            mod day_01;
+            #[cfg(feature = "day02")]
+            mod day_02;
+            #[cfg(feature = "day11")]
+            mod day_11;
+
+            pub mod geom;
+        "#};
+        assert_eq!(
+            register_module(lib, 3, "day_03").unwrap(),
+            lib.replace(
+                "mod day_02;\n",
+                "mod day_02;\n#[cfg(feature = \"day03\")]\nmod day_03;\n"
+            )
+        );
+        assert_eq!(
+            register_module(lib, 12, "day_12").unwrap(),
+            lib.replace(
+                "mod day_11;\n",
+                "mod day_11;\n#[cfg(feature = \"day12\")]\nmod day_12;\n"
+            )
+        );
+        assert!(register_module(lib, 2, "day_02").is_err());
+    }
+
+    #[test]
+    fn test_register_solutions() {
+        let solutions = indoc! {r#"
+            pub const SOLUTIONS: &[Solution] = &[
+                #[cfg(feature = "day01")]
+                solution!(1, 1, day_01, input_generator, part1),
+            ];
+        "#};
+        assert_eq!(
+            register_solutions(solutions, 12, "day_12").unwrap(),
+            indoc! {r#"
+                pub const SOLUTIONS: &[Solution] = &[
+                    #[cfg(feature = "day01")]
+                    solution!(1, 1, day_01, input_generator, part1),
+                    #[cfg(feature = "day12")]
+                    solution!(12, 1, day_12, input_generator, part1),
+                    #[cfg(feature = "day12")]
+                    solution!(12, 2, day_12, input_generator, part2),
+                ];
+            "#}
+        );
+
+        let solutions = register_solutions(solutions, 12, "day_12").unwrap();
+        assert_eq!(
+            register_solutions(&solutions, 3, "day_03").unwrap(),
+            solutions.replacen(
+                "part1),\n    #[cfg(feature = \"day12\")]",
+                "part1),\
+                 \n    #[cfg(feature = \"day03\")]\
+                 \n    solution!(3, 1, day_03, input_generator, part1),\
+                 \n    #[cfg(feature = \"day03\")]\
+                 \n    solution!(3, 2, day_03, input_generator, part2),\
+                 \n    #[cfg(feature = \"day12\")]",
+                1
+            )
+        );
+    }
+}
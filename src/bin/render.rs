@@ -0,0 +1,122 @@
+use aoc2024::input::{self, parse_arg, Flags};
+use aoc2024::render::{self, DAYS};
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+const USAGE: &str = "usage: render <day> [--input <path>|-] [--output <path>] [--scale <n>]
+
+Draws the grid of <day>'s input as an image, written as PNG if --output ends in .png (which needs
+the `png` feature) and as PPM otherwise. Without --input it reads input/2024/day<day>.txt, and
+without --output it writes day<day>.ppm. Each cell is drawn as a --scale by --scale square
+(default 4).";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    day: u8,
+    input: Option<String>,
+    output: Option<String>,
+    scale: usize,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let mut args = Flags::new(args);
+    let mut day = None;
+    let mut parsed = Args {
+        day: 0,
+        input: None,
+        output: None,
+        scale: 4,
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--input" | "-i" => parsed.input = Some(args.value("--input")?),
+            "--output" | "-o" => parsed.output = Some(args.value("--output")?),
+            "--scale" | "-s" => {
+                let v = args.value("--scale")?;
+                parsed.scale = v
+                    .parse()
+                    .ok()
+                    .filter(|scale| *scale > 0)
+                    .ok_or_else(|| format!("invalid scale {v:?}"))?;
+            }
+            _ if day.is_none() => day = Some(parse_arg(&arg, "day")?),
+            _ => return Err(format!("unexpected argument {arg:?}")),
+        }
+    }
+
+    parsed.day = day.ok_or("missing day")?;
+    Ok(parsed)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let path = args
+        .input
+        .unwrap_or_else(|| format!("input/2024/day{}.txt", args.day));
+    let input = input::read(&path).map_err(|e| format!("{path}: {e}"))?;
+
+    let image = render::render(args.day, &input)
+        .ok_or_else(|| format!("day {} can't be rendered", args.day))?
+        .map_err(|e| format!("{path}: {e}"))?;
+
+    let output = args
+        .output
+        .unwrap_or_else(|| format!("day{}.ppm", args.day));
+    image
+        .scaled(args.scale)
+        .save(Path::new(&output))
+        .map_err(|e| format!("{output}: {e}"))
+}
+
+fn usage() -> String {
+    let days: Vec<String> = DAYS.iter().map(u8::to_string).collect();
+    format!("{USAGE}\n\nDays that can be rendered: {}", days.join(", "))
+}
+
+fn main() -> ExitCode {
+    if env::args().any(|a| a == "--help" || a == "-h") {
+        println!("{}", usage());
+        return ExitCode::SUCCESS;
+    }
+
+    match parse_args(env::args().skip(1)).and_then(run) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("error: {e}\n\n{}", usage());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Result<Args, String> {
+        parse_args(s.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        assert_eq!(
+            args("6 -i big.txt --output day6.png --scale 2").unwrap(),
+            Args {
+                day: 6,
+                input: Some(String::from("big.txt")),
+                output: Some(String::from("day6.png")),
+                scale: 2,
+            }
+        );
+
+        let parsed = args("10").unwrap();
+        assert_eq!((parsed.input, parsed.output, parsed.scale), (None, None, 4));
+    }
+
+    #[test]
+    fn test_parse_args_invalid() {
+        assert_eq!(args("").unwrap_err(), "missing day");
+        assert_eq!(args("6 --scale 0").unwrap_err(), "invalid scale \"0\"");
+        assert_eq!(args("6 -o").unwrap_err(), "missing value for --output");
+    }
+}
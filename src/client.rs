@@ -0,0 +1,430 @@
+use regex::Regex;
+use std::cell::Cell;
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use std::{fs, io, thread};
+use ureq::Agent;
+
+#[cfg(test)]
+mod mock;
+
+const USER_AGENT: &str = concat!("aoc2024/", env!("CARGO_PKG_VERSION"), " (ureq)");
+
+/// A problem talking to the Advent of Code site or caching what it returned.
+#[derive(Debug)]
+pub enum ClientError {
+    Io(io::Error),
+    /// The site answered with a non-success status, e.g. 400 for a missing or expired session.
+    Status(u16),
+    Transport(String),
+    /// A submission was refused locally because the site asked us to wait this much longer.
+    RateLimited(Duration),
+    /// The response didn't match anything we know how to interpret.
+    UnexpectedResponse(String),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ClientError::Io(e) => write!(f, "{e}"),
+            ClientError::Status(400) => write!(f, "HTTP 400, is the session cookie valid?"),
+            ClientError::Status(status) => write!(f, "HTTP {status}"),
+            ClientError::Transport(e) => write!(f, "{e}"),
+            ClientError::RateLimited(wait) => {
+                write!(
+                    f,
+                    "rate limited, wait {}s before submitting",
+                    wait.as_secs()
+                )
+            }
+            ClientError::UnexpectedResponse(text) => write!(f, "unexpected response: {text}"),
+        }
+    }
+}
+
+impl Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+impl From<ureq::Error> for ClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::StatusCode(status) => ClientError::Status(status),
+            ureq::Error::Io(e) => ClientError::Io(e),
+            e => ClientError::Transport(e.to_string()),
+        }
+    }
+}
+
+/// What the site made of a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint in either direction.
+    Incorrect,
+    /// Submitted too soon after the previous answer; try again after the wait.
+    TooRecent(Duration),
+    /// The part is already solved, or part 2 isn't unlocked yet.
+    WrongLevel,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::TooHigh => write!(f, "incorrect, too high"),
+            Verdict::TooLow => write!(f, "incorrect, too low"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooRecent(wait) => write!(f, "too recent, wait {}s", wait.as_secs()),
+            Verdict::WrongLevel => write!(f, "already solved or not unlocked"),
+        }
+    }
+}
+
+static ARTICLE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap());
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]*>").unwrap());
+static LEFT_TO_WAIT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap());
+static WAIT_MINUTES: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"wait (one|\d+) minutes? before trying again").unwrap());
+
+/// The text of the `<article>` an answer page reports its verdict in, without markup.
+fn article_text(html: &str) -> String {
+    let article = ARTICLE
+        .captures(html)
+        .map_or(html, |c| c.get(1).unwrap().as_str());
+    TAG.replace_all(article, "").trim().to_string()
+}
+
+/// How long the site asks us to wait before the next submission, if it says.
+fn wait_time(text: &str) -> Option<Duration> {
+    if let Some(c) = LEFT_TO_WAIT.captures(text) {
+        let minutes: u64 = c.get(1).map_or(0, |m| m.as_str().parse().unwrap());
+        let seconds: u64 = c[2].parse().unwrap();
+        return Some(Duration::from_secs(minutes * 60 + seconds));
+    }
+
+    WAIT_MINUTES.captures(text).map(|c| {
+        let minutes = if &c[1] == "one" {
+            1
+        } else {
+            c[1].parse().unwrap()
+        };
+        Duration::from_secs(minutes * 60)
+    })
+}
+
+/// Interprets the page returned for a submitted answer.
+pub fn parse_verdict(html: &str) -> Result<Verdict, ClientError> {
+    let text = article_text(html);
+
+    if text.contains("That's the right answer") {
+        Ok(Verdict::Correct)
+    } else if text.contains("That's not the right answer") {
+        Ok(if text.contains("your answer is too high") {
+            Verdict::TooHigh
+        } else if text.contains("your answer is too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        })
+    } else if text.contains("You gave an answer too recently") {
+        Ok(Verdict::TooRecent(wait_time(&text).unwrap_or_default()))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Ok(Verdict::WrongLevel)
+    } else {
+        Err(ClientError::UnexpectedResponse(text))
+    }
+}
+
+/// Downloads inputs into the `input/<year>/day<day>.txt` layout and submits answers, spacing
+/// requests at least `min_interval` apart.
+pub struct Client {
+    agent: Agent,
+    base_url: String,
+    session: String,
+    cache_dir: PathBuf,
+    min_interval: Duration,
+    last_request: Cell<Option<Instant>>,
+    retry_after: Cell<Option<Instant>>,
+}
+
+impl Client {
+    /// A client for adventofcode.com authenticated by the `session` cookie, caching inputs under
+    /// `input/`.
+    pub fn new(session: &str) -> Self {
+        Client::with_base_url("https://adventofcode.com", session)
+    }
+
+    pub fn with_base_url(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .timeout_global(Some(Duration::from_secs(30)))
+            .user_agent(USER_AGENT)
+            .build()
+            .into();
+
+        Client {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            cache_dir: PathBuf::from("input"),
+            min_interval: Duration::from_secs(5),
+            last_request: Cell::new(None),
+            retry_after: Cell::new(None),
+        }
+    }
+
+    pub fn cache_dir(mut self, dir: impl AsRef<Path>) -> Self {
+        self.cache_dir = dir.as_ref().to_path_buf();
+        self
+    }
+
+    pub fn min_interval(mut self, interval: Duration) -> Self {
+        self.min_interval = interval;
+        self
+    }
+
+    /// Where the input for `day` is cached.
+    pub fn input_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("day{day}.txt"))
+    }
+
+    /// The input for `day`, read from the cache or downloaded and cached on first use.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let path = self.input_path(year, day);
+        match fs::read_to_string(&path) {
+            Ok(input) => return Ok(input),
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            Err(_) => {}
+        }
+
+        self.throttle();
+        let input = self
+            .agent
+            .get(format!("{}/{year}/day/{day}/input", self.base_url))
+            .header("Cookie", format!("session={}", self.session))
+            .call()?
+            .body_mut()
+            .read_to_string()?;
+
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, &input)?;
+        Ok(input)
+    }
+
+    /// Submits `answer` for one part of `day`. Submissions made while the site has asked us to
+    /// wait are refused without sending anything.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Verdict, ClientError> {
+        if let Some(wait) = self
+            .retry_after
+            .get()
+            .and_then(|at| at.checked_duration_since(Instant::now()))
+        {
+            return Err(ClientError::RateLimited(wait));
+        }
+
+        self.throttle();
+        let level = part.to_string();
+        let html = self
+            .agent
+            .post(format!("{}/{year}/day/{day}/answer", self.base_url))
+            .header("Cookie", format!("session={}", self.session))
+            .send_form([("level", level.as_str()), ("answer", answer)])?
+            .body_mut()
+            .read_to_string()?;
+
+        let verdict = parse_verdict(&html)?;
+        self.retry_after
+            .set(wait_time(&article_text(&html)).map(|wait| Instant::now() + wait));
+        Ok(verdict)
+    }
+
+    /// Sleeps until `min_interval` has passed since the previous request.
+    fn throttle(&self) {
+        if let Some(wait) = self
+            .last_request
+            .get()
+            .map(|at| at + self.min_interval)
+            .and_then(|at| at.checked_duration_since(Instant::now()))
+        {
+            thread::sleep(wait);
+        }
+        self.last_request.set(Some(Instant::now()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::MockServer;
+    use super::*;
+    use std::env;
+
+    fn answer_page(message: &str) -> String {
+        format!("<html><body><main><article><p>{message}</p></article></main></body></html>")
+    }
+
+    fn cache_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc2024-client-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_parse_verdict() {
+        assert_eq!(
+            parse_verdict(&answer_page(
+                "That's the right answer!  You are <span>one gold star</span> closer."
+            ))
+            .unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            parse_verdict(&answer_page(
+                "That's not the right answer; your answer is too high.  Please wait one minute before trying again."
+            ))
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict(&answer_page(
+                "That's not the right answer; your answer is too low."
+            ))
+            .unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict(&answer_page("That's not the right answer.")).unwrap(),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_verdict(&answer_page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            ))
+            .unwrap(),
+            Verdict::TooRecent(Duration::from_secs(65))
+        );
+        assert_eq!(
+            parse_verdict(&answer_page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            ))
+            .unwrap(),
+            Verdict::WrongLevel
+        );
+        assert!(matches!(
+            parse_verdict("<html>maintenance</html>"),
+            Err(ClientError::UnexpectedResponse(text)) if text == "maintenance"
+        ));
+    }
+
+    #[test]
+    fn test_wait_time() {
+        assert_eq!(
+            wait_time("You have 36s left to wait."),
+            Some(Duration::from_secs(36))
+        );
+        assert_eq!(
+            wait_time("please wait 5 minutes before trying again."),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(wait_time("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn test_input() {
+        let server = MockServer::start(|request| match request.path.as_str() {
+            "/2024/day/9/input" => (200, String::from("2333133121414131402\n")),
+            _ => (404, String::from("not found")),
+        });
+        let dir = cache_dir("input");
+        let client = Client::with_base_url(&server.url, "abc")
+            .cache_dir(&dir)
+            .min_interval(Duration::ZERO);
+
+        assert_eq!(client.input(2024, 9).unwrap(), "2333133121414131402\n");
+        assert_eq!(
+            fs::read_to_string(dir.join("2024/day9.txt")).unwrap(),
+            "2333133121414131402\n"
+        );
+
+        // the second read is served from the cache
+        assert_eq!(client.input(2024, 9).unwrap(), "2333133121414131402\n");
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].cookie.as_deref(), Some("session=abc"));
+
+        assert!(matches!(
+            client.input(2024, 10),
+            Err(ClientError::Status(404))
+        ));
+        assert!(!dir.join("2024/day10.txt").exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = MockServer::start(|request| {
+            let page = match request.body.as_str() {
+                "level=1&answer=1928" => "That's the right answer!",
+                "level=2&answer=1" => {
+                    "That's not the right answer; your answer is too low.  Please wait one minute before trying again."
+                }
+                _ => "You don't seem to be solving the right level.",
+            };
+            (200, answer_page(page))
+        });
+        let client = Client::with_base_url(&server.url, "abc")
+            .cache_dir(cache_dir("submit"))
+            .min_interval(Duration::ZERO);
+
+        assert_eq!(client.submit(2024, 9, 1, "1928").unwrap(), Verdict::Correct);
+        assert_eq!(client.submit(2024, 9, 2, "1").unwrap(), Verdict::TooLow);
+
+        // the site asked for a minute's wait, so the next submission never leaves the client
+        assert!(matches!(
+            client.submit(2024, 9, 2, "2858"),
+            Err(ClientError::RateLimited(wait)) if wait > Duration::from_secs(50)
+        ));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2024/day/9/answer");
+    }
+
+    #[test]
+    fn test_throttle() {
+        let server = MockServer::start(|_| (200, String::from("input")));
+        let dir = cache_dir("throttle");
+        let client = Client::with_base_url(&server.url, "abc")
+            .cache_dir(&dir)
+            .min_interval(Duration::from_millis(200));
+
+        let start = Instant::now();
+        client.input(2024, 1).unwrap();
+        client.input(2024, 2).unwrap();
+        assert!(start.elapsed() >= Duration::from_millis(200));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
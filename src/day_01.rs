@@ -1,17 +1,33 @@
+use crate::error::{parse_number, ParseError, ReadError};
+use crate::input::{self, normalize};
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
 use std::collections::HashMap;
+use std::io::BufRead;
 use std::iter::zip;
 
-#[aoc_generator(day1)]
-pub fn input_generator(input: &str) -> (Vec<u32>, Vec<u32>) {
-    let pairs: Vec<(u32, u32)> = input
-        .lines()
-        .map(|l| {
-            let nums: Vec<u32> = l.split_whitespace().map(|x| x.parse().unwrap()).collect();
-            (nums[0], nums[1])
-        })
-        .collect();
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    3   4
+    4   3
+    2   5
+    1   3
+    3   9
+    3   3
+"};
 
+fn parse_pair(i: usize, l: &str) -> Result<(u32, u32), ParseError> {
+    let nums: Vec<u32> = l
+        .split_whitespace()
+        .map(|x| parse_number(i, l, x))
+        .collect::<Result<_, _>>()?;
+    match nums[..] {
+        [a, b] => Ok((a, b)),
+        _ => Err(ParseError::at(i, l, l, "expected two location ids")),
+    }
+}
+
+fn sorted_lists(pairs: &[(u32, u32)]) -> (Vec<u32>, Vec<u32>) {
     let mut first: Vec<u32> = pairs.iter().map(|(a, _b)| *a).collect();
     let mut second: Vec<u32> = pairs.iter().map(|(_a, b)| *b).collect();
 
@@ -21,19 +37,38 @@ pub fn input_generator(input: &str) -> (Vec<u32>, Vec<u32>) {
     (first, second)
 }
 
+#[aoc_generator(day1)]
+pub fn input_generator(input: &str) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
+    let input = &normalize(input);
+    let pairs: Vec<(u32, u32)> = input
+        .lines()
+        .enumerate()
+        .map(|(i, l)| parse_pair(i, l))
+        .collect::<Result<_, _>>()?;
+
+    Ok(sorted_lists(&pairs))
+}
+
+/// `input_generator` for input read a line at a time, without holding all of it in memory.
+pub fn read_input(reader: impl BufRead) -> Result<(Vec<u32>, Vec<u32>), ReadError> {
+    let pairs: Vec<(u32, u32)> = input::lines(reader)
+        .enumerate()
+        .map(|(i, l)| Ok(parse_pair(i, &l?)?))
+        .collect::<Result<_, ReadError>>()?;
+
+    Ok(sorted_lists(&pairs))
+}
+
 #[aoc(day1, part1)]
-pub fn solve_part1(input: &(Vec<u32>, Vec<u32>)) -> u32 {
+pub fn solve_part1(input: &(Vec<u32>, Vec<u32>)) -> u64 {
     let (left, right) = input;
     zip(left, right)
-        .map(|(l, r)| {
-            let diff: i32 = *l as i32 - *r as i32;
-            diff.unsigned_abs()
-        })
+        .map(|(l, r)| u64::from(l.abs_diff(*r)))
         .sum()
 }
 
 #[aoc(day1, part2)]
-pub fn solve_part2(input: &(Vec<u32>, Vec<u32>)) -> u32 {
+pub fn solve_part2(input: &(Vec<u32>, Vec<u32>)) -> u64 {
     fn counts(nums: &[u32]) -> HashMap<u32, u32> {
         nums.iter().fold(HashMap::new(), |mut acc, x| {
             acc.entry(*x).and_modify(|cnt| *cnt += 1).or_insert(1);
@@ -47,7 +82,104 @@ pub fn solve_part2(input: &(Vec<u32>, Vec<u32>)) -> u32 {
     left.iter()
         .map(|(val, times)| {
             let cnt = right.get(val).unwrap_or(&0);
-            val * times * cnt
+            u64::from(*val) * u64::from(*times) * u64::from(*cnt)
         })
         .sum()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    /// Pairs the lists by repeatedly taking the smallest remaining id from each.
+    fn part1_naive(pairs: &[(u32, u32)]) -> u64 {
+        let mut left: Vec<u32> = pairs.iter().map(|(l, _)| *l).collect();
+        let mut right: Vec<u32> = pairs.iter().map(|(_, r)| *r).collect();
+        let mut total = 0;
+        while !left.is_empty() {
+            let take_min = |list: &mut Vec<u32>| {
+                let (i, _) = list.iter().enumerate().min_by_key(|(_, v)| **v).unwrap();
+                list.remove(i)
+            };
+            total += u64::from(take_min(&mut left).abs_diff(take_min(&mut right)));
+        }
+        total
+    }
+
+    /// Counts each left id's appearances in the right list directly.
+    fn part2_naive(pairs: &[(u32, u32)]) -> u64 {
+        pairs
+            .iter()
+            .map(|(l, _)| u64::from(*l) * pairs.iter().filter(|(_, r)| r == l).count() as u64)
+            .sum()
+    }
+
+    #[test]
+    fn test_input_generator() {
+        let (left, right) = input_generator(EXAMPLE).unwrap();
+        assert_eq!(left, vec![1, 2, 3, 3, 3, 4]);
+        assert_eq!(right, vec![3, 3, 3, 4, 5, 9]);
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("3   4\n4   x3\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 5, "x3", "expected a number"));
+
+        let err = input_generator("3   4\n4\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 1, "4", "expected two location ids"));
+    }
+
+    #[test]
+    fn test_read_input() {
+        let expected = input_generator(EXAMPLE).unwrap();
+        assert_eq!(read_input(EXAMPLE.as_bytes()).unwrap(), expected);
+        let crlf = windows_style(EXAMPLE);
+        assert_eq!(read_input(crlf.as_bytes()).unwrap(), expected);
+
+        let err = read_input("3   4\n4   x3\n".as_bytes()).unwrap_err();
+        let ReadError::Parse(err) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(err, input_generator("3   4\n4   x3\n").unwrap_err());
+    }
+
+    #[test]
+    fn test_large_ids() {
+        let input = input_generator("4294967295   0\n4294967295   4294967295").unwrap();
+        assert_eq!(solve_part1(&input), 4294967295);
+        assert_eq!(solve_part2(&input), 2 * 4294967295);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(solve_part1(&input), 11);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = solve_part1(&input);
+        assert_eq!(result, 11);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = solve_part2(&input);
+        assert_eq!(result, 31);
+    }
+
+    proptest! {
+        #[test]
+        fn test_solvers_match_naive(pairs in prop::collection::vec((1u32..50, 1u32..50), 1..40)) {
+            let text: Vec<String> = pairs.iter().map(|(l, r)| format!("{l}   {r}")).collect();
+            let input = input_generator(&text.join("\n")).unwrap();
+            prop_assert_eq!(solve_part1(&input), part1_naive(&pairs));
+            prop_assert_eq!(solve_part2(&input), part2_naive(&pairs));
+        }
+    }
+}
@@ -1,4 +1,19 @@
+use crate::error::{parse_number, ParseError, ReadError};
+use crate::input::{self, normalize};
+use crate::parallel::Mode;
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+use std::io::BufRead;
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    7 6 4 2 1
+    1 2 7 8 9
+    9 7 6 2 1
+    1 3 2 4 5
+    8 6 4 4 1
+    1 3 6 7 9
+"};
 
 #[derive(Debug, PartialOrd, PartialEq, Clone)]
 pub struct Report {
@@ -14,50 +29,86 @@ impl Report {
         self.levels.windows(2).all(|w| w[0] >= w[1])
     }
 
-    fn diff_min_max(&self) -> (u32, u32) {
+    /// The smallest and largest step, or `None` for a report of a single level.
+    fn diff_min_max(&self) -> Option<(u32, u32)> {
         let diffs: Vec<u32> = self
             .levels
             .windows(2)
             .map(|w| w[0].abs_diff(w[1]))
             .collect();
-        let min = diffs.iter().min().unwrap();
-        let max = diffs.iter().max().unwrap();
+        let min = diffs.iter().min()?;
+        let max = diffs.iter().max()?;
 
-        (*min, *max)
+        Some((*min, *max))
     }
 
     pub fn safe(&self) -> bool {
         let sorted = self.all_decreasing() || self.all_increasing();
-        let (min, max) = self.diff_min_max();
+        let Some((min, max)) = self.diff_min_max() else {
+            return true;
+        };
 
         sorted && min >= 1 && max <= 3
     }
 
+    /// Whether removing at most one level leaves a safe report, checked in linear time: only the
+    /// two levels of the first bad step in a direction can be the one to remove.
     pub fn dampened_safe(&self) -> bool {
-        if self.safe() {
-            return true;
-        }
-        for i in 0..self.levels.len() {
-            let mut r = self.clone();
-            r.levels.remove(i);
-            if r.safe() {
-                return true;
+        [true, false].into_iter().any(|increasing| {
+            match self
+                .levels
+                .windows(2)
+                .position(|w| !safe_step(w[0], w[1], increasing))
+            {
+                None => true,
+                Some(i) => self.safe_without(i, increasing) || self.safe_without(i + 1, increasing),
             }
-        }
-        false
+        })
+    }
+
+    fn safe_without(&self, skip: usize, increasing: bool) -> bool {
+        let levels: Vec<u32> = self
+            .levels
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != skip)
+            .map(|(_, l)| *l)
+            .collect();
+        levels.windows(2).all(|w| safe_step(w[0], w[1], increasing))
+    }
+}
+
+fn safe_step(a: u32, b: u32, increasing: bool) -> bool {
+    let (from, to) = if increasing { (a, b) } else { (b, a) };
+    to > from && to - from <= 3
+}
+
+fn parse_report(i: usize, line: &str) -> Result<Report, ParseError> {
+    let levels: Vec<u32> = line
+        .split_whitespace()
+        .map(|num| parse_number(i, line, num))
+        .collect::<Result<_, _>>()?;
+    if levels.is_empty() {
+        return Err(ParseError::at(i, line, line, "expected a report of levels"));
     }
+    Ok(Report { levels })
 }
 
 #[aoc_generator(day2)]
-pub fn input_generator(input: &str) -> Vec<Report> {
+pub fn input_generator(input: &str) -> Result<Vec<Report>, ParseError> {
+    let input = &normalize(input);
     input
         .lines()
-        .map(|line| Report {
-            levels: line
-                .split_whitespace()
-                .map(|num| num.parse().unwrap())
-                .collect(),
-        })
+        .enumerate()
+        .map(|(i, line)| parse_report(i, line))
+        .collect()
+}
+
+/// `input_generator` for input read a line at a time, without holding all of it in memory.
+pub fn read_input(reader: impl BufRead) -> Result<Vec<Report>, ReadError> {
+    input::lines(reader)
+        .enumerate()
+        .map(|(i, line)| Ok(parse_report(i, &line?)?))
         .collect()
 }
 
@@ -68,26 +119,49 @@ pub fn part1(input: &[Report]) -> usize {
 
 #[aoc(day2, part2)]
 pub fn part2(input: &[Report]) -> usize {
-    input.iter().filter(|r| r.dampened_safe()).count()
+    count_dampened_safe(input, Mode::default())
+}
+
+fn count_dampened_safe(input: &[Report], mode: Mode) -> usize {
+    mode.count(input, |r| r.dampened_safe())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
 
-    const TEST_INPUT: &str = indoc! {"
-        7 6 4 2 1
-        1 2 7 8 9
-        9 7 6 2 1
-        1 3 2 4 5
-        8 6 4 4 1
-        1 3 6 7 9
-    "};
+    /// Tries removing every level in turn.
+    fn dampened_safe_naive(report: &Report) -> bool {
+        report.safe()
+            || (0..report.levels.len()).any(|i| {
+                let mut r = report.clone();
+                r.levels.remove(i);
+                r.safe()
+            })
+    }
+
+    fn reports() -> impl Strategy<Value = String> {
+        // small levels so that safe and nearly safe reports are common
+        prop::collection::vec(prop::collection::vec(1u32..12, 3..9), 1..20).prop_map(|reports| {
+            reports
+                .iter()
+                .map(|levels| {
+                    levels
+                        .iter()
+                        .map(u32::to_string)
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
 
     #[test]
     fn test_input_generator() {
-        let reports = input_generator(TEST_INPUT);
+        let reports = input_generator(EXAMPLE).unwrap();
         let exp: Vec<Report> = vec![
             Report {
                 levels: vec![7, 6, 4, 2, 1],
@@ -112,17 +186,92 @@ mod tests {
         assert_eq!(reports, exp);
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("7 6 4 2 1\n1 2 -7 8 9\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 5, "-7", "expected a number"));
+
+        let err = input_generator("7 6 4 2 1\n\t\n1 2\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "", "expected a report of levels")
+        );
+    }
+
+    #[test]
+    fn test_read_input() {
+        let expected = input_generator(EXAMPLE).unwrap();
+        assert_eq!(read_input(EXAMPLE.as_bytes()).unwrap(), expected);
+        let crlf = windows_style(EXAMPLE);
+        assert_eq!(read_input(crlf.as_bytes()).unwrap(), expected);
+
+        let err = read_input("7 6 4 2 1\n\t\n1 2\n".as_bytes()).unwrap_err();
+        let ReadError::Parse(err) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "", "expected a report of levels")
+        );
+    }
+
+    #[test]
+    fn test_single_level() {
+        let reports = input_generator("7\n1 9").unwrap();
+        assert_eq!(part1(&reports), 1);
+        assert_eq!(part2(&reports), 2);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 2);
+    }
+
     #[test]
     fn test_part_one() {
-        let reports = input_generator(TEST_INPUT);
+        let reports = input_generator(EXAMPLE).unwrap();
         let result = part1(reports.as_slice());
         assert_eq!(result, 2);
     }
 
     #[test]
     fn test_part_two() {
-        let reports = input_generator(TEST_INPUT);
+        let reports = input_generator(EXAMPLE).unwrap();
         let result = part2(reports.as_slice());
         assert_eq!(result, 4);
     }
+
+    #[test]
+    fn test_dampened_safe() {
+        // only the levels either side of the first bad step are tried, in both directions
+        let safe = |levels: &str| input_generator(levels).unwrap()[0].dampened_safe();
+        assert!(safe("9 1 2 3 4"));
+        assert!(safe("1 2 3 4 9"));
+        assert!(safe("4 5 3 2 1"));
+        assert!(safe("1 2 9 3 4"));
+        assert!(safe("5 1 6 7 8"));
+        assert!(!safe("1 2 9 9 3"));
+        assert!(!safe("1 5 9 13 17"));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_part_two_parallel() {
+        let reports = input_generator(EXAMPLE).unwrap();
+        assert_eq!(
+            count_dampened_safe(&reports, Mode::Parallel),
+            count_dampened_safe(&reports, Mode::Serial)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_part_two_matches_naive(input in reports()) {
+            let reports = input_generator(&input).unwrap();
+            prop_assert_eq!(&read_input(input.as_bytes()).unwrap(), &reports);
+            let expected = reports.iter().filter(|r| dampened_safe_naive(r)).count();
+            prop_assert_eq!(part2(&reports), expected);
+        }
+    }
 }
@@ -1,28 +1,49 @@
+use crate::error::{ParseError, ReadError};
+use crate::input::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use regex::Regex;
+use std::io::{self, BufRead};
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str =
+    "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+
+// Corrupted memory is free-form: anything that isn't a well-formed instruction is skipped, so
+// these generators never actually fail. They return `Result` to match every other day.
 
 #[aoc_generator(day3, part1)]
-pub fn input_generator_one(input: &str) -> Vec<(u32, u32)> {
-    let re = Regex::new(r"mul\((\d{1,3}),(\d{1,3})\)").unwrap();
+pub fn input_generator_one(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    let input = &normalize(input);
+    Ok(instructions(input))
+}
+
+#[aoc_generator(day3, part2)]
+pub fn input_generator_two(input: &str) -> Result<Vec<(u32, u32)>, ParseError> {
+    let input = &normalize(input);
+    Ok(enabled_instructions(input))
+}
+
+fn instructions(input: &str) -> Vec<(u32, u32)> {
+    // `\d` would also match non-ASCII digits, which don't parse as a `u32`
+    let re = Regex::new(r"mul\(([0-9]{1,3}),([0-9]{1,3})\)").unwrap();
     re.captures_iter(input)
         .map(|caps| (caps[1].parse().unwrap(), caps[2].parse().unwrap()))
         .collect()
 }
 
-#[aoc_generator(day3, part2)]
-pub fn input_generator_two(input: &str) -> Vec<(u32, u32)> {
+fn enabled_instructions(input: &str) -> Vec<(u32, u32)> {
     let dont_re = Regex::new(r"don't\(\)").unwrap();
     let do_re = Regex::new(r"do\(\)").unwrap();
     let mut res = vec![];
 
     match dont_re.find(input) {
-        None => res.extend(input_generator_one(input)),
+        None => res.extend(instructions(input)),
         Some(m) => {
-            res.extend(input_generator_one(&input[..m.start()]));
+            res.extend(instructions(&input[..m.start()]));
 
             let remaining = &input[m.end()..];
             if let Some(m2) = do_re.find(remaining) {
-                res.extend(input_generator_two(&remaining[m2.end()..]));
+                res.extend(enabled_instructions(&remaining[m2.end()..]));
             }
         }
     }
@@ -30,22 +51,166 @@ pub fn input_generator_two(input: &str) -> Vec<(u32, u32)> {
     res
 }
 
+/// Finds instructions in corrupted memory fed to it a chunk at a time, one byte at a time, so an
+/// instruction split between chunks is found all the same. It finds the same instructions as the
+/// regexes above: a partial match only ever contains its token's first letter at its start, so
+/// after a mismatch scanning can resume at the mismatched byte.
+#[derive(Debug, Clone, PartialEq)]
+struct Scanner {
+    /// How far into `mul(a,b)` the bytes so far match: 0 to 3 bytes of `mul(`, then 4 in the
+    /// first number and 5 in the second.
+    mul: usize,
+    numbers: (u32, u32),
+    digits: usize,
+    /// How many bytes of `don't()` the bytes so far match, or 7 after `do(`.
+    toggle: usize,
+    enabled: bool,
+    /// Every `mul` found.
+    all: Vec<(u32, u32)>,
+    /// The `mul`s not disabled by a `don't()`.
+    enabled_only: Vec<(u32, u32)>,
+}
+
+impl Default for Scanner {
+    fn default() -> Self {
+        Scanner {
+            mul: 0,
+            numbers: (0, 0),
+            digits: 0,
+            toggle: 0,
+            enabled: true,
+            all: vec![],
+            enabled_only: vec![],
+        }
+    }
+}
+
+impl Scanner {
+    fn feed(&mut self, chunk: &[u8]) {
+        chunk.iter().for_each(|&b| {
+            self.scan_mul(b);
+            self.scan_toggle(b);
+        });
+    }
+
+    fn scan_mul(&mut self, b: u8) {
+        const PREFIX: &[u8] = b"mul(";
+
+        let matched = match (self.mul, b) {
+            (0..4, _) => PREFIX[self.mul] == b,
+            (4 | 5, b'0'..=b'9') if self.digits < 3 => {
+                let n = if self.mul == 4 {
+                    &mut self.numbers.0
+                } else {
+                    &mut self.numbers.1
+                };
+                *n = *n * 10 + u32::from(b - b'0');
+                self.digits += 1;
+                // stay put in the number
+                return;
+            }
+            (4, b',') => self.digits > 0,
+            (5, b')') if self.digits > 0 => {
+                self.all.push(self.numbers);
+                if self.enabled {
+                    self.enabled_only.push(self.numbers);
+                }
+                false
+            }
+            _ => false,
+        };
+
+        if matched {
+            if self.mul == 4 {
+                // moving on to the second number
+                self.digits = 0;
+            }
+            self.mul += 1;
+        } else {
+            self.mul = 0;
+            self.numbers = (0, 0);
+            self.digits = 0;
+            if b == PREFIX[0] {
+                self.mul = 1;
+            }
+        }
+    }
+
+    fn scan_toggle(&mut self, b: u8) {
+        const DONT: &[u8] = b"don't()";
+
+        self.toggle = match (self.toggle, b) {
+            // `do(` branches off after `do`
+            (2, b'(') => 7,
+            (7, b')') => {
+                self.enabled = true;
+                0
+            }
+            (6, b')') => {
+                self.enabled = false;
+                0
+            }
+            (i @ 0..6, b) if DONT[i] == b => i + 1,
+            (_, b'd') => 1,
+            _ => 0,
+        };
+    }
+}
+
+/// Scans `reader` a buffer at a time.
+fn scan(mut reader: impl BufRead) -> io::Result<Scanner> {
+    let mut scanner = Scanner::default();
+    loop {
+        let chunk = reader.fill_buf()?;
+        if chunk.is_empty() {
+            return Ok(scanner);
+        }
+        scanner.feed(chunk);
+        let len = chunk.len();
+        reader.consume(len);
+    }
+}
+
+/// `input_generator_one` for memory read a buffer at a time, without holding all of it in memory.
+pub fn read_input_one(reader: impl BufRead) -> Result<Vec<(u32, u32)>, ReadError> {
+    Ok(scan(reader)?.all)
+}
+
+/// `input_generator_two` for memory read a buffer at a time, without holding all of it in memory.
+pub fn read_input_two(reader: impl BufRead) -> Result<Vec<(u32, u32)>, ReadError> {
+    Ok(scan(reader)?.enabled_only)
+}
+
 #[aoc(day3, part1)]
 #[aoc(day3, part2)]
-pub fn solution(input: &[(u32, u32)]) -> u32 {
-    input.iter().map(|(x, y)| x * y).sum()
+pub fn solution(input: &[(u32, u32)]) -> u64 {
+    input.iter().map(|(x, y)| u64::from(x * y)).sum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+    use std::io::BufReader;
 
-    const TEST_INPUT: &str =
-        "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+    /// Reads `memory` through a buffer of `capacity` bytes, so that chunks end every `capacity`
+    /// bytes.
+    fn chunked(memory: &str, capacity: usize) -> impl BufRead + '_ {
+        BufReader::with_capacity(capacity, memory.as_bytes())
+    }
+
+    fn memory() -> impl Strategy<Value = String> {
+        let token = prop::sample::select(vec![
+            "mul(", "mul", "m", "(", ")", ",", "1", "23", "456", "7890", "do()", "don't()", "do",
+            "don't", "'", "x", " ", "\n", "é",
+        ]);
+        prop::collection::vec(token, 0..40).prop_map(|tokens| tokens.concat())
+    }
 
     #[test]
     fn test_input_generator_one() {
-        let res = input_generator_one(TEST_INPUT);
+        let res = input_generator_one(EXAMPLE).unwrap();
         let exp = vec![(2, 4), (5, 5), (11, 8), (8, 5)];
 
         assert_eq!(res, exp);
@@ -53,15 +218,60 @@ mod tests {
 
     #[test]
     fn test_input_generator_two() {
-        let res = input_generator_two(TEST_INPUT);
+        let res = input_generator_two(EXAMPLE).unwrap();
         let exp = vec![(2, 4), (8, 5)];
 
         assert_eq!(res, exp);
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let res = input_generator_one("mul(1234,5)mul(2, 3)mul(4,5\r\n").unwrap();
+        assert_eq!(res, vec![]);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator_one(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(solution(&input), 161);
+        let input = input_generator_two(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(solution(&input), 48);
+    }
+
+    #[test]
+    fn test_read_input() {
+        (1..=EXAMPLE.len()).for_each(|capacity| {
+            let one = read_input_one(chunked(EXAMPLE, capacity)).unwrap();
+            assert_eq!(one, input_generator_one(EXAMPLE).unwrap());
+            let two = read_input_two(chunked(EXAMPLE, capacity)).unwrap();
+            assert_eq!(two, input_generator_two(EXAMPLE).unwrap());
+        });
+
+        let memory = "mul(1234,5)mul(2, 3)mul(4,5mul(6,7)don'tdo()don't(do())mul(8,9)";
+        let two = read_input_two(chunked(memory, 1)).unwrap();
+        assert_eq!(two, vec![(6, 7), (8, 9)]);
+        assert_eq!(two, input_generator_two(memory).unwrap());
+    }
+
+    #[test]
+    fn test_non_ascii_digits() {
+        // Arabic-Indic digits are digits to `\d`, but not to `parse`
+        assert_eq!(input_generator_one("mul(\u{663},4)").unwrap(), vec![]);
+    }
+
+    proptest! {
+        #[test]
+        fn test_read_input_matches(memory in memory(), capacity in 1usize..16) {
+            let one = read_input_one(chunked(&memory, capacity)).unwrap();
+            prop_assert_eq!(one, input_generator_one(&memory).unwrap());
+            let two = read_input_two(chunked(&memory, capacity)).unwrap();
+            prop_assert_eq!(two, input_generator_two(&memory).unwrap());
+        }
+    }
+
     #[test]
     fn test_part_one() {
-        let input = input_generator_one(TEST_INPUT);
+        let input = input_generator_one(EXAMPLE).unwrap();
         let result = solution(input.as_slice());
 
         assert_eq!(result, 161);
@@ -69,9 +279,15 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let input = input_generator_two(TEST_INPUT);
+        let input = input_generator_two(EXAMPLE).unwrap();
         let result = solution(input.as_slice());
 
         assert_eq!(result, 48);
     }
+
+    #[test]
+    fn test_solution_large() {
+        let input = input_generator_one(&"mul(999,999)".repeat(5000)).unwrap();
+        assert_eq!(solution(&input), 5000 * 999 * 999);
+    }
 }
@@ -1,109 +1,138 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::input::normalize;
+use crate::render::{Image, Rgb};
 use aoc_runner_derive::{aoc, aoc_generator};
-
-#[derive(Debug, PartialOrd, PartialEq, Clone)]
+use indoc::indoc;
+use std::collections::HashSet;
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    MMMSXXMASM
+    MSAMXMSMSA
+    AMXSXMAAMM
+    MSAMASMSMX
+    XMASAMXAMM
+    XXAMMXXAMA
+    SMSMSASXSS
+    SAXAMASAAA
+    MAMMMXMMMM
+    MXMXAXMASX
+"};
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct Puzzle {
-    chars: Vec<Vec<char>>,
+    chars: Grid<char>,
 }
 
 impl Puzzle {
-    pub fn new(input: &str) -> Self {
-        let chars: Vec<Vec<char>> = input.lines().map(|line| line.chars().collect()).collect();
-        Puzzle { chars }
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let chars = Grid::parse(input, |c| "XMAS".contains(c).then_some(c))?;
+        Ok(Puzzle { chars })
     }
 
-    pub fn get(&self, i: usize, j: usize) -> Option<char> {
-        self.chars.get(i).and_then(|row| row.get(j)).cloned()
+    pub fn get(&self, pos: Point) -> Option<char> {
+        self.chars.get(pos).cloned()
     }
 
-    pub fn find(&self, c: char) -> Vec<(usize, usize)> {
-        self.chars
-            .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().filter_map(
-                    move |(j, &ch)| {
-                        if ch == c {
-                            Some((i, j))
-                        } else {
-                            None
-                        }
-                    },
-                )
-            })
-            .collect()
+    pub fn find(&self, c: char) -> Vec<Point> {
+        self.chars.positions(|&ch| ch == c).collect()
     }
 
-    fn check_direction(&self, offset: (i32, i32), start: (i32, i32), chars: &[char]) -> bool {
+    fn check_direction(&self, direction: Direction, start: Point, chars: &[char]) -> bool {
         if chars.is_empty() {
             return true;
         }
-        let c = chars[0];
-
-        let (idx, jdx) = offset;
-        let (i, j) = start;
 
-        if i < 0 || j < 0 {
-            return false;
-        }
-
-        if let Some(c2) = self.get(i as usize, j as usize) {
-            if c == c2 {
-                return self.check_direction(offset, (i + idx, j + jdx), &chars[1..]);
+        if let Some(c) = self.get(start) {
+            if c == chars[0] {
+                return self.check_direction(direction, start + direction, &chars[1..]);
             }
         }
 
         false
     }
 
-    pub fn check(&self, start: (usize, usize)) -> usize {
-        let directions = vec![
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ];
-
+    pub fn check(&self, start: Point) -> usize {
         let chars: Vec<char> = "XMAS".chars().collect();
 
-        let i = start.0 as i32;
-        let j = start.1 as i32;
-
-        directions
+        Direction::ALL
             .into_iter()
-            .filter(|direction| self.check_direction(*direction, (i, j), chars.as_slice()))
+            .filter(|direction| self.check_direction(*direction, start, chars.as_slice()))
             .count()
     }
 
-    pub fn check_for_x(&self, a: (usize, usize)) -> bool {
-        let (i, j) = a;
-        if i == 0 || j == 0 {
-            return false;
-        }
+    pub fn check_for_x(&self, a: Point) -> bool {
+        let corners = |d1: Direction, d2: Direction| -> String {
+            [
+                self.get(a + d1).unwrap_or('Z'),
+                self.get(a + d2).unwrap_or('Z'),
+            ]
+            .iter()
+            .collect()
+        };
 
-        let chars1: String = [
-            self.get(i - 1, j - 1).unwrap_or('Z'),
-            self.get(i + 1, j + 1).unwrap_or('Z'),
-        ]
-        .iter()
-        .collect();
-
-        let chars2: String = [
-            self.get(i - 1, j + 1).unwrap_or('Z'),
-            self.get(i + 1, j - 1).unwrap_or('Z'),
-        ]
-        .iter()
-        .collect();
+        let chars1 = corners(Direction::UpLeft, Direction::DownRight);
+        let chars2 = corners(Direction::UpRight, Direction::DownLeft);
 
         [chars1, chars2].iter().all(|cs| cs == "SM" || cs == "MS")
     }
+
+    /// The cells that are part of an XMAS, and those that are part of an X-MAS.
+    fn hits(&self) -> (HashSet<Point>, HashSet<Point>) {
+        let chars: Vec<char> = "XMAS".chars().collect();
+        let chars = chars.as_slice();
+        let xmas = self
+            .find('X')
+            .into_iter()
+            .flat_map(|x| {
+                Direction::ALL
+                    .into_iter()
+                    .filter(move |d| self.check_direction(*d, x, chars))
+                    .flat_map(move |d| (0..4).map(move |i| x + d.offset() * i))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        let x_mas = self
+            .find('A')
+            .into_iter()
+            .filter(|a| self.check_for_x(*a))
+            .flat_map(|a| {
+                [
+                    Direction::UpLeft,
+                    Direction::UpRight,
+                    Direction::DownLeft,
+                    Direction::DownRight,
+                ]
+                .map(|d| a + d)
+                .into_iter()
+                .chain([a])
+            })
+            .collect();
+
+        (xmas, x_mas)
+    }
+}
+
+/// The word search with the letters of each XMAS in yellow, of each X-MAS in blue, and of both
+/// in white.
+pub fn render(puzzle: &Puzzle) -> Image {
+    let (xmas, x_mas) = puzzle.hits();
+    Image::from_grid(&puzzle.chars, |point, _| {
+        match (xmas.contains(&point), x_mas.contains(&point)) {
+            (true, true) => Rgb::WHITE,
+            (true, false) => Rgb(255, 200, 0),
+            (false, true) => Rgb(0, 160, 255),
+            (false, false) => Rgb::grey(40),
+        }
+    })
 }
 
 #[aoc_generator(day4)]
-pub fn input_generator(input: &str) -> Puzzle {
+pub fn input_generator(input: &str) -> Result<Puzzle, ParseError> {
+    let input = &normalize(input);
     Puzzle::new(input)
 }
 
@@ -128,47 +157,54 @@ pub fn part2(input: &Puzzle) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        MMMSXXMASM
-        MSAMXMSMSA
-        AMXSXMAAMM
-        MSAMASMSMX
-        XMASAMXAMM
-        XXAMMXXAMA
-        SMSMSASXSS
-        SAXAMASAAA
-        MAMMMXMMMM
-        MXMXAXMASX
-    "};
+    use crate::input::windows_style;
 
     #[test]
     fn test_input_generator() {
-        let puzzle = input_generator(TEST_INPUT);
-        assert_eq!(puzzle.chars.len(), 10);
-        assert_eq!(puzzle.chars[0].len(), 10);
+        let puzzle = input_generator(EXAMPLE).unwrap();
+        assert_eq!(puzzle.chars.dimensions(), (10, 10));
+        assert_eq!(puzzle.chars.to_string(), EXAMPLE);
+    }
 
-        let s: String = puzzle.chars[0].iter().collect();
-        assert_eq!(s.as_str(), "MMMSXXMASM");
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("XMAS\nXMAZ\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 4, "Z", "unexpected character"));
     }
 
     #[test]
     fn test_get() {
-        let puzzle = Puzzle::new(TEST_INPUT);
-        assert_eq!(puzzle.get(2, 0), Some('A'));
+        let puzzle = Puzzle::new(EXAMPLE).unwrap();
+        assert_eq!(puzzle.get(Point::new(0, 2)), Some('A'));
+    }
+
+    #[test]
+    fn test_render() {
+        let image = render(&input_generator(EXAMPLE).unwrap());
+        assert_eq!(image.dimensions(), (10, 10));
+        // XMAS along the top row, an X-MAS corner at (1, 0), a letter of both, and an M in neither
+        assert_eq!(image.pixel(5, 0), Some(Rgb(255, 200, 0)));
+        assert_eq!(image.pixel(1, 0), Some(Rgb(0, 160, 255)));
+        assert_eq!(image.pixel(2, 1), Some(Rgb::WHITE));
+        assert_eq!(image.pixel(0, 0), Some(Rgb::grey(40)));
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 18);
     }
 
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 18);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 9);
     }
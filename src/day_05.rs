@@ -1,10 +1,46 @@
+use crate::error::{parse_number, ParseError};
+use crate::export::Graph;
+use crate::input::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
 use std::collections::{HashMap, HashSet};
 
-type RuleSet = HashMap<u32, Page>;
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    47|53
+    97|13
+    97|61
+    97|47
+    75|29
+    61|13
+    75|53
+    29|13
+    97|29
+    53|29
+    61|53
+    97|53
+    61|29
+    47|13
+    75|47
+    97|75
+    47|61
+    75|61
+    47|29
+    75|13
+    53|13
+
+    75,47,61,53,29
+    97,61,53,29,13
+    75,29,13
+    75,97,47,61,53
+    61,13,29
+    97,13,75,29,47
+"};
+
+pub type RuleSet = HashMap<u32, Page>;
 
 #[derive(Debug, PartialEq, Clone, Default)]
-struct Page {
+pub struct Page {
     num: u32,
     before: HashSet<u32>,
     after: HashSet<u32>,
@@ -12,7 +48,10 @@ struct Page {
 
 impl Page {
     fn valid(&self, rules: &RuleSet) -> bool {
-        let p_rules = rules.get(&self.num).unwrap();
+        // a page that no rule mentions can go anywhere
+        let Some(p_rules) = rules.get(&self.num) else {
+            return true;
+        };
         self.before.intersection(&p_rules.after).count() == 0
             && self.after.intersection(&p_rules.before).count() == 0
     }
@@ -34,13 +73,26 @@ impl PageList {
         self.pages.iter().all(|page| page.valid(rules))
     }
 
+    /// The rules `before|after` that the update breaks by having `after` earlier than `before`.
+    fn violations(&self, rules: &RuleSet) -> HashSet<(u32, u32)> {
+        self.pages
+            .iter()
+            .filter_map(|page| Some((page, rules.get(&page.num)?)))
+            .flat_map(|(page, p_rules)| {
+                page.before
+                    .intersection(&p_rules.after)
+                    .map(|after| (page.num, *after))
+            })
+            .collect()
+    }
+
     fn page_nums(&self) -> Vec<u32> {
         self.pages.iter().map(|p| p.num).collect()
     }
 
-    fn middle_page(&self) -> u32 {
+    fn middle_page(&self) -> u64 {
         let idx = self.pages.len() / 2;
-        self.pages[idx].num
+        u64::from(self.pages[idx].num)
     }
 
     fn sorted(&self, rules: &RuleSet) -> PageList {
@@ -49,7 +101,8 @@ impl PageList {
         self.page_nums().iter().enumerate().for_each(|(i, num)| {
             ordered.push(*num);
             let mut idx = i;
-            while !PageList::new(ordered.as_slice()).valid(rules) {
+            // with contradictory rules there may be no valid position, so stop at the front
+            while idx > 0 && !PageList::new(ordered.as_slice()).valid(rules) {
                 ordered.swap(idx, idx - 1);
                 idx -= 1;
             }
@@ -79,43 +132,86 @@ impl PageList {
     }
 }
 
-#[aoc_generator(day5)]
-fn input_generator(input: &str) -> (RuleSet, Vec<PageList>) {
-    let (input1, input2) = input.split_once("\n\n").unwrap();
+/// The rules as a graph with an edge from each page to every page that must come after it. With
+/// an `update`, its pages and the rules it breaks are highlighted.
+pub fn rule_graph(rules: &RuleSet, update: Option<&PageList>) -> Graph {
+    let (pages, violations) = match update {
+        Some(update) => (update.page_nums(), update.violations(rules)),
+        None => (vec![], HashSet::new()),
+    };
 
-    let rule_set = input1.lines().fold(HashMap::new(), |mut map, line| {
-        let (before_s, after_s) = line.split_once("|").unwrap();
-        let before = before_s.parse().unwrap();
-        let after = after_s.parse().unwrap();
+    let mut nums: Vec<u32> = rules.keys().copied().collect();
+    nums.sort();
 
-        let page = map.entry(before).or_insert(Page {
-            num: before,
-            ..Default::default()
+    let mut graph = Graph::new("rules");
+    nums.iter().for_each(|num| {
+        graph.add_node(&num.to_string(), &num.to_string(), pages.contains(num));
+    });
+    nums.iter().for_each(|before| {
+        let mut after: Vec<u32> = rules[before].after.iter().copied().collect();
+        after.sort();
+        after.iter().for_each(|after| {
+            let broken = violations.contains(&(*before, *after));
+            graph.add_edge(&before.to_string(), &after.to_string(), broken);
         });
-        page.after.insert(after);
+    });
+    graph
+}
 
-        let page = map.entry(after).or_insert(Page {
-            num: after,
-            ..Default::default()
-        });
-        page.before.insert(before);
+#[aoc_generator(day5)]
+pub fn input_generator(input: &str) -> Result<(RuleSet, Vec<PageList>), ParseError> {
+    let input = &normalize(input);
+    let (input1, input2) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::eof(
+            input,
+            "expected a blank line between the rules and the updates",
+        )
+    })?;
 
-        map
-    });
+    let rule_set = input1
+        .lines()
+        .enumerate()
+        .try_fold(HashMap::new(), |mut map, (i, line)| {
+            let (before_s, after_s) = line
+                .split_once("|")
+                .ok_or_else(|| ParseError::at(i, line, line, "expected a rule like 47|53"))?;
+            let before = parse_number(i, line, before_s)?;
+            let after = parse_number(i, line, after_s)?;
+
+            let page = map.entry(before).or_insert(Page {
+                num: before,
+                ..Default::default()
+            });
+            page.after.insert(after);
 
+            let page = map.entry(after).or_insert(Page {
+                num: after,
+                ..Default::default()
+            });
+            page.before.insert(before);
+
+            Ok(map)
+        })?;
+
+    // updates start after the rules and the blank separator line
+    let offset = input1.lines().count() + 1;
     let page_lists = input2
         .lines()
-        .map(|l| {
-            let nums: Vec<u32> = l.split(',').map(|x| x.parse().unwrap()).collect();
-            PageList::new(nums.as_slice())
+        .enumerate()
+        .map(|(i, l)| {
+            let nums: Vec<u32> = l
+                .split(',')
+                .map(|x| parse_number(offset + i, l, x))
+                .collect::<Result<_, _>>()?;
+            Ok(PageList::new(nums.as_slice()))
         })
-        .collect();
+        .collect::<Result<_, _>>()?;
 
-    (rule_set, page_lists)
+    Ok((rule_set, page_lists))
 }
 
 #[aoc(day5, part1)]
-fn part1(input: &(RuleSet, Vec<PageList>)) -> u32 {
+pub fn part1(input: &(RuleSet, Vec<PageList>)) -> u64 {
     let (rules, updates) = input;
     updates
         .iter()
@@ -125,7 +221,7 @@ fn part1(input: &(RuleSet, Vec<PageList>)) -> u32 {
 }
 
 #[aoc(day5, part2)]
-fn part2(input: &(RuleSet, Vec<PageList>)) -> u32 {
+pub fn part2(input: &(RuleSet, Vec<PageList>)) -> u64 {
     let (rules, updates) = input;
     updates
         .iter()
@@ -137,56 +233,74 @@ fn part2(input: &(RuleSet, Vec<PageList>)) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        47|53
-        97|13
-        97|61
-        97|47
-        75|29
-        61|13
-        75|53
-        29|13
-        97|29
-        53|29
-        61|53
-        97|53
-        61|29
-        47|13
-        75|47
-        97|75
-        47|61
-        75|61
-        47|29
-        75|13
-        53|13
-
-        75,47,61,53,29
-        97,61,53,29,13
-        75,29,13
-        75,97,47,61,53
-        61,13,29
-        97,13,75,29,47
-    "};
+    use crate::input::windows_style;
 
     #[test]
     fn test_input_generator() {
-        let (map, list) = input_generator(TEST_INPUT);
+        let (map, list) = input_generator(EXAMPLE).unwrap();
         assert_eq!(map.len(), 7);
         assert_eq!(list.len(), 6);
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("47|53\n97-13\n\n75,47\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "97-13", "expected a rule like 47|53")
+        );
+
+        let err = input_generator("47|53\n\n75,47,\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 7, "", "expected a number"));
+
+        let err = input_generator("47|53\n75,47\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                3,
+                1,
+                "",
+                "expected a blank line between the rules and the updates"
+            )
+        );
+    }
+
+    #[test]
+    fn test_unruled_pages() {
+        // 13 has no rules, and 29|47 contradicts 47|29
+        let input = input_generator("47|29\n29|47\n\n13,47,29\n47,13\n").unwrap();
+        assert_eq!(part1(&input), 13);
+        // there is no valid order for the first update, so sorting gives up at 29,13,47
+        assert_eq!(part2(&input), 13);
+    }
+
+    #[test]
+    fn test_violations() {
+        let (rules, updates) = input_generator(EXAMPLE).unwrap();
+        assert!(updates[0].violations(&rules).is_empty());
+        assert_eq!(updates[3].violations(&rules), HashSet::from([(97, 75)]));
+        assert_eq!(
+            updates[5].violations(&rules),
+            HashSet::from([(75, 13), (29, 13), (47, 13), (47, 29)])
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 143);
+    }
+
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 143);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 123);
     }
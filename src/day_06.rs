@@ -1,224 +1,377 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::input::normalize;
+use crate::parallel::Mode;
+use crate::render::{Image, Rgb};
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
 use std::collections::{HashMap, HashSet};
+use std::fmt::{self, Formatter};
 
-type Point = (i32, i32);
-
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
-struct MapLocation {
-    point: Point,
-}
-
-impl MapLocation {
-    fn location_at(&self, direction: &Direction) -> MapLocation {
-        let (i, j) = self.point;
-        let (idx, jdx) = direction.offset();
-        MapLocation {
-            point: (i + idx, j + jdx),
-        }
-    }
-}
-
-#[derive(Default, PartialEq, Clone, Debug, Hash, Eq)]
-enum Direction {
-    #[default]
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn offset(&self) -> Point {
-        match self {
-            Direction::Up => (-1, 0),
-            Direction::Down => (1, 0),
-            Direction::Left => (0, -1),
-            Direction::Right => (0, 1),
-        }
-    }
-
-    fn rotate(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-            Direction::Right => Direction::Down,
-        }
-    }
-}
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    ....#.....
+    .........#
+    ..........
+    ..#.......
+    .......#..
+    ..........
+    .#..^.....
+    ........#.
+    #.........
+    ......#...
+"};
 
 #[derive(Default, PartialEq, Clone, Debug)]
 struct GuardPosition {
-    location: MapLocation,
+    location: Point,
     direction: Direction,
 }
 
 impl GuardPosition {
     fn rotate(&mut self) {
-        self.direction = self.direction.rotate();
+        self.direction = self.direction.rotate_cw();
+    }
+
+    fn ahead(&self) -> Point {
+        self.location + self.direction
     }
 }
 
 #[derive(Default, Clone, Debug)]
-struct ObstructionMap {
-    dimensions: (usize, usize),
-    obstructions: HashSet<MapLocation>,
+pub struct ObstructionMap {
+    obstructions: Grid<bool>,
     starting_position: GuardPosition,
 }
 
 impl ObstructionMap {
-    fn off_map(&self, loc: &MapLocation) -> bool {
-        let (i, j) = loc.point;
-        let (max_i, max_j) = self.dimensions;
+    fn off_map(&self, loc: Point) -> bool {
+        !self.obstructions.contains(loc)
+    }
 
-        i < 0 || j < 0 || i >= max_i as i32 || j >= max_j as i32
+    fn obstructed(&self, loc: Point) -> bool {
+        self.obstructions.get(loc) == Some(&true)
     }
-    fn insert(&mut self, point: (usize, usize)) {
-        let (i, j) = point;
-        let location = MapLocation {
-            point: (i as i32, j as i32),
-        };
-        self.obstructions.insert(location);
+
+    fn insert(&mut self, point: Point) {
+        if let Some(cell) = self.obstructions.get_mut(point) {
+            *cell = true;
+        }
     }
 
-    fn start_guard(&mut self, point: (usize, usize)) {
-        let (i, j) = point;
-        let location = MapLocation {
-            point: (i as i32, j as i32),
-        };
+    fn start_guard(&mut self, point: Point) {
         self.starting_position = GuardPosition {
-            location,
+            location: point,
             ..Default::default()
         };
     }
 }
 
 #[aoc_generator(day6)]
-fn input_generator(input: &str) -> ObstructionMap {
-    let lines: Vec<&str> = input.lines().collect();
+pub fn input_generator(input: &str) -> Result<ObstructionMap, ParseError> {
+    let input = &normalize(input);
+    let chars = Grid::parse(input, |c| matches!(c, '.' | '#' | '^').then_some(c))?;
     let mut ob_map = ObstructionMap {
-        dimensions: (lines.len(), lines[0].len()),
+        obstructions: chars.map(|&c| c == '#'),
         ..Default::default()
     };
 
-    lines.into_iter().enumerate().for_each(|(i, l)| {
-        l.chars().enumerate().for_each(|(j, c)| match c {
-            '#' => ob_map.insert((i, j)),
-            '^' => ob_map.start_guard((i, j)),
-            _ => {}
-        })
-    });
+    let start = chars
+        .find(|&c| c == '^')
+        .ok_or_else(|| ParseError::eof(input, "expected a guard '^' on the map"))?;
+    ob_map.start_guard(start);
 
-    ob_map
+    Ok(ob_map)
 }
 
-#[aoc(day6, part1)]
-fn part1(input: &ObstructionMap) -> usize {
-    let mut visited: HashSet<Point> = HashSet::new();
-    let mut pos = input.starting_position.clone();
+/// The guard's patrol one move or turn at a time, ending when they leave the map or walk the same
+/// way through the same location twice on a map they never leave.
+#[derive(Clone, Debug)]
+pub struct Patrol<'a> {
+    map: &'a ObstructionMap,
+    guard: GuardPosition,
+    visited: HashSet<Point>,
+    states: HashSet<(Point, Direction)>,
+}
 
-    loop {
-        visited.insert(pos.location.point);
-        let loc = pos.location.location_at(&pos.direction);
-        if input.off_map(&loc) {
-            break;
+impl<'a> Patrol<'a> {
+    pub fn new(map: &'a ObstructionMap) -> Self {
+        let guard = map.starting_position.clone();
+        Patrol {
+            map,
+            visited: HashSet::from([guard.location]),
+            states: HashSet::from([(guard.location, guard.direction)]),
+            guard,
         }
+    }
+}
 
-        if input.obstructions.contains(&loc) {
-            pos.rotate();
+impl Iterator for Patrol<'_> {
+    /// Where the guard is after the step.
+    type Item = Point;
+
+    fn next(&mut self) -> Option<Point> {
+        let loc = self.guard.ahead();
+        if self.map.off_map(loc) {
+            return None;
+        }
+
+        if self.map.obstructed(loc) {
+            self.guard.rotate();
         } else {
-            pos.location = loc;
+            self.guard.location = loc;
         }
+
+        self.visited.insert(self.guard.location);
+        self.states
+            .insert((self.guard.location, self.guard.direction))
+            .then_some(self.guard.location)
     }
+}
 
-    visited.len()
+impl fmt::Display for Patrol<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut chars = self.map.obstructions.map(|&o| if o { '#' } else { '.' });
+        self.visited.iter().for_each(|&loc| {
+            if let Some(c) = chars.get_mut(loc) {
+                *c = 'X';
+            }
+        });
+        if let Some(c) = chars.get_mut(self.guard.location) {
+            *c = match self.guard.direction {
+                Direction::Right => '>',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+                _ => '^',
+            };
+        }
+        chars.fmt(f)
+    }
+}
+
+/// Every location the guard visits before leaving the map, or before walking the same way
+/// through the same location twice on a map they never leave.
+fn patrol(input: &ObstructionMap) -> HashSet<Point> {
+    let mut patrol = Patrol::new(input);
+    patrol.by_ref().for_each(drop);
+    patrol.visited
+}
+
+/// The map with obstructions in black, the guard's route in red and where they start in green.
+pub fn render(input: &ObstructionMap) -> Image {
+    let route = patrol(input);
+    Image::from_grid(&input.obstructions, |point, &obstructed| match obstructed {
+        true => Rgb::grey(30),
+        false if point == input.starting_position.location => Rgb(40, 200, 60),
+        false if route.contains(&point) => Rgb(220, 50, 50),
+        false => Rgb::grey(230),
+    })
+}
+
+#[aoc(day6, part1)]
+pub fn part1(input: &ObstructionMap) -> usize {
+    patrol(input).len()
 }
 
 #[aoc(day6, part2)]
-fn part2(input: &ObstructionMap) -> usize {
-    let (i_len, j_len) = input.dimensions;
-    let points: Vec<(i32, i32)> = (0..i_len as i32)
-        .flat_map(|i| {
-            (0..j_len as i32)
-                .map(|j| (i, j))
-                .collect::<Vec<(i32, i32)>>()
-        })
-        .collect();
+pub fn part2(input: &ObstructionMap) -> usize {
+    count_loops(input, Mode::default())
+}
 
-    points
+fn count_loops(input: &ObstructionMap, mode: Mode) -> usize {
+    // an obstruction off the guard's original route never gets walked into
+    let candidates: Vec<Point> = patrol(input)
         .into_iter()
-        .map(|(i, j)| MapLocation { point: (i, j) })
-        .filter(|loc| *loc != input.starting_position.location && !input.obstructions.contains(loc))
-        .filter(|obs| {
-            // create a map with one addition obstruction
-            let mut obs_map = input.clone();
-            obs_map.obstructions.insert(obs.clone());
-
-            // track locations visited *and* the directions traveled through them
-            let mut visited: HashMap<Point, HashSet<Direction>> = HashMap::new();
-            let mut pos = obs_map.starting_position.clone();
-
-            loop {
-                let traveled_directions = visited.entry(pos.location.point).or_default();
-                if traveled_directions.contains(&pos.direction) {
-                    //loop detected
-                    return true;
-                } else {
-                    traveled_directions.insert(pos.direction.clone());
-                }
-
-                let loc = pos.location.location_at(&pos.direction);
-                if obs_map.off_map(&loc) {
-                    // the new obstruction did not create a loop
-                    return false;
-                }
-
-                if obs_map.obstructions.contains(&loc) {
-                    pos.rotate();
-                } else {
-                    pos.location = loc;
-                }
-            }
-        })
-        .count()
+        .filter(|loc| *loc != input.starting_position.location)
+        .collect();
+
+    mode.count(&candidates, |obs| creates_loop(input, *obs))
+}
+
+fn creates_loop(input: &ObstructionMap, obs: Point) -> bool {
+    // create a map with one addition obstruction
+    let mut obs_map = input.clone();
+    obs_map.insert(obs);
+
+    // track locations visited *and* the directions traveled through them
+    let mut visited: HashMap<Point, HashSet<Direction>> = HashMap::new();
+    let mut pos = obs_map.starting_position.clone();
+
+    loop {
+        let traveled_directions = visited.entry(pos.location).or_default();
+        if traveled_directions.contains(&pos.direction) {
+            //loop detected
+            return true;
+        } else {
+            traveled_directions.insert(pos.direction);
+        }
+
+        let loc = pos.ahead();
+        if obs_map.off_map(loc) {
+            // the new obstruction did not create a loop
+            return false;
+        }
+
+        if obs_map.obstructed(loc) {
+            pos.rotate();
+        } else {
+            pos.location = loc;
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        ....#.....
-        .........#
-        ..........
-        ..#.......
-        .......#..
-        ..........
-        .#..^.....
-        ........#.
-        #.........
-        ......#...
-    "};
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    /// Tries an obstruction in every free cell.
+    fn part2_naive(input: &ObstructionMap) -> usize {
+        input
+            .obstructions
+            .positions(|&obstructed| !obstructed)
+            .filter(|loc| *loc != input.starting_position.location)
+            .filter(|obs| creates_loop(input, *obs))
+            .count()
+    }
+
+    fn maps() -> impl Strategy<Value = String> {
+        (1usize..10, 1usize..10)
+            .prop_flat_map(|(rows, cols)| {
+                (
+                    prop::collection::vec(prop::bool::weighted(0.2), rows * cols),
+                    0..rows * cols,
+                    Just(cols),
+                )
+            })
+            .prop_map(|(cells, guard, cols)| {
+                cells
+                    .chunks(cols)
+                    .enumerate()
+                    .map(|(row, chunk)| {
+                        chunk
+                            .iter()
+                            .enumerate()
+                            .map(|(col, &obstructed)| match row * cols + col {
+                                i if i == guard => '^',
+                                _ if obstructed => '#',
+                                _ => '.',
+                            })
+                            .collect::<String>()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+    }
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
-        assert_eq!(input.obstructions.len(), 8);
-        assert_eq!(input.starting_position.location.point, (6, 4));
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.obstructions.positions(|&o| o).count(), 8);
+        assert_eq!(input.starting_position.location, Point::new(4, 6));
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("..#\n.^.\n.>.\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 2, ">", "unexpected character"));
+
+        let err = input_generator("..#\n...\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(3, 1, "", "expected a guard '^' on the map")
+        );
+    }
+
+    #[test]
+    fn test_trapped_guard() {
+        let input = input_generator(".#.\n#^#\n.#.").unwrap();
+        assert_eq!(part1(&input), 1);
+        assert_eq!(part2(&input), 0);
+
+        // the guard walks a square around the middle of the map forever
+        let input = input_generator(".#..\n.^.#\n#...\n..#.").unwrap();
+        assert_eq!(part1(&input), 4);
+    }
+
+    #[test]
+    fn test_render() {
+        let image = render(&input_generator(EXAMPLE).unwrap());
+        assert_eq!(image.dimensions(), (10, 10));
+        assert_eq!(image.pixel(4, 0), Some(Rgb::grey(30)));
+        assert_eq!(image.pixel(4, 6), Some(Rgb(40, 200, 60)));
+        assert_eq!(image.pixel(4, 1), Some(Rgb(220, 50, 50)));
+        assert_eq!(image.pixel(0, 0), Some(Rgb::grey(230)));
+    }
+
+    #[test]
+    fn test_patrol_steps() {
+        let map = input_generator(EXAMPLE).unwrap();
+        let mut patrol = Patrol::new(&map);
+        assert_eq!(patrol.next(), Some(Point::new(4, 5)));
+        assert_eq!(patrol.nth(3), Some(Point::new(4, 1)));
+        assert_eq!(patrol.next(), Some(Point::new(4, 1)));
+        assert_eq!(
+            patrol.to_string().lines().take(3).collect::<Vec<_>>(),
+            ["....#.....", "....>....#", "....X....."]
+        );
+        assert_eq!(patrol.count(), 48);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 41);
     }
 
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 41);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 6);
     }
+
+    #[test]
+    fn test_loops_only_on_route() {
+        // part two only tries obstructions on the guard's route, as none of the others make a loop
+        let input = input_generator(EXAMPLE).unwrap();
+        let route = patrol(&input);
+        let off_route: Vec<Point> = input
+            .obstructions
+            .positions(|&obstructed| !obstructed)
+            .filter(|loc| !route.contains(loc))
+            .collect();
+        assert_eq!(off_route.len(), 100 - 8 - 41);
+        assert!(!off_route.iter().any(|&obs| creates_loop(&input, obs)));
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_part_two_parallel() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(
+            count_loops(&input, Mode::Parallel),
+            count_loops(&input, Mode::Serial)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_part_two_matches_naive(input in maps()) {
+            let map = input_generator(&input).unwrap();
+            // only a guard who leaves the map can be made to loop, so skip maps that trap them;
+            // adding an obstruction off the map leaves it as it is
+            prop_assume!(!creates_loop(&map, Point::new(-1, -1)));
+            prop_assert_eq!(part2(&map), part2_naive(&map));
+        }
+    }
 }
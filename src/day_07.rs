@@ -1,7 +1,25 @@
+use crate::error::{parse_number, ParseError, ReadError};
+use crate::input::{self, normalize};
+use crate::parallel::Mode;
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+use std::io::BufRead;
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    190: 10 19
+    3267: 81 40 27
+    83: 17 5
+    156: 15 6
+    7290: 6 8 6 15
+    161011: 16 10 13
+    192: 17 8 14
+    21037: 9 7 18 13
+    292: 11 6 16 20
+"};
 
 #[derive(PartialEq, Debug)]
-struct Calibration {
+pub struct Calibration {
     total: u64,
     values: Vec<u64>,
 }
@@ -14,11 +32,12 @@ enum Operator {
 }
 
 impl Operator {
-    fn apply(&self, a: u64, b: u64) -> u64 {
+    /// The result of `a op b`, or `None` if it doesn't fit in a `u64`.
+    fn apply(&self, a: u64, b: u64) -> Option<u64> {
         match self {
-            Operator::Add => a + b,
-            Operator::Mult => a * b,
-            Operator::Concat => format!("{a}{b}").parse().unwrap(),
+            Operator::Add => a.checked_add(b),
+            Operator::Mult => a.checked_mul(b),
+            Operator::Concat => format!("{a}{b}").parse().ok(),
         }
     }
 }
@@ -31,65 +50,119 @@ fn possible(total: u64, ops: &[Operator], acc: u64, values: &[u64]) -> bool {
         return false;
     }
 
-    ops.iter()
-        .any(|op| possible(total, ops, op.apply(acc, values[0]), &values[1..]))
+    // a result too big for a u64 is bigger than the total, so that branch can't match
+    ops.iter().any(|op| {
+        op.apply(acc, values[0])
+            .is_some_and(|acc| possible(total, ops, acc, &values[1..]))
+    })
 }
 
-fn solve(input: &[Calibration], ops: &[Operator]) -> u64 {
-    input
-        .iter()
-        .filter(|c| possible(c.total, ops, 0, c.values.as_slice()))
-        .map(|c| c.total)
-        .sum()
+/// The sum of the possible totals, which can exceed a `u64` when the totals are large.
+fn solve(input: &[Calibration], ops: &[Operator], mode: Mode) -> u128 {
+    mode.sum(input, |c| match c.values.split_first() {
+        Some((first, rest)) if possible(c.total, ops, *first, rest) => u128::from(c.total),
+        _ => 0,
+    })
+}
+
+fn parse_calibration(i: usize, l: &str) -> Result<Calibration, ParseError> {
+    let (total_s, values_s) = l
+        .split_once(':')
+        .ok_or_else(|| ParseError::at(i, l, l, "expected a calibration like 190: 10 19"))?;
+    Ok(Calibration {
+        total: parse_number(i, l, total_s)?,
+        values: values_s
+            .split_whitespace()
+            .map(|v| parse_number(i, l, v))
+            .collect::<Result<_, _>>()?,
+    })
 }
 
 #[aoc_generator(day7)]
-fn input_generator(input: &str) -> Vec<Calibration> {
+pub fn input_generator(input: &str) -> Result<Vec<Calibration>, ParseError> {
+    let input = &normalize(input);
     input
         .lines()
-        .map(|l| {
-            let (total_s, values_s) = l.split_once(": ").unwrap();
-            Calibration {
-                total: total_s.parse().unwrap(),
-                values: values_s
-                    .split_whitespace()
-                    .map(|v| v.parse().unwrap())
-                    .collect(),
-            }
-        })
+        .enumerate()
+        .map(|(i, l)| parse_calibration(i, l))
+        .collect()
+}
+
+/// `input_generator` for input read a line at a time, without holding all of it in memory.
+pub fn read_input(reader: impl BufRead) -> Result<Vec<Calibration>, ReadError> {
+    input::lines(reader)
+        .enumerate()
+        .map(|(i, l)| Ok(parse_calibration(i, &l?)?))
         .collect()
 }
 
 #[aoc(day7, part1)]
-fn part1(input: &[Calibration]) -> u64 {
-    solve(input, &[Operator::Add, Operator::Mult])
+pub fn part1(input: &[Calibration]) -> u128 {
+    solve(input, &[Operator::Add, Operator::Mult], Mode::default())
 }
 
 #[aoc(day7, part2)]
-fn part2(input: &[Calibration]) -> u64 {
-    solve(input, &[Operator::Add, Operator::Mult, Operator::Concat])
+pub fn part2(input: &[Calibration]) -> u128 {
+    solve(
+        input,
+        &[Operator::Add, Operator::Mult, Operator::Concat],
+        Mode::default(),
+    )
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        190: 10 19
-        3267: 81 40 27
-        83: 17 5
-        156: 15 6
-        7290: 6 8 6 15
-        161011: 16 10 13
-        192: 17 8 14
-        21037: 9 7 18 13
-        292: 11 6 16 20
-    "};
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    const ALL: [Operator; 3] = [Operator::Add, Operator::Mult, Operator::Concat];
+
+    /// The `combination`-th way of placing `ops` between `values`, evaluated left to right.
+    fn evaluate(values: &[u64], ops: &[Operator], mut combination: usize) -> Option<u64> {
+        values[1..].iter().try_fold(values[0], |acc, v| {
+            let op = &ops[combination % ops.len()];
+            combination /= ops.len();
+            op.apply(acc, *v)
+        })
+    }
+
+    fn combinations(values: &[u64], ops: &[Operator]) -> usize {
+        ops.len().pow(values.len() as u32 - 1)
+    }
+
+    /// Evaluates every combination of operators.
+    fn possible_naive(c: &Calibration, ops: &[Operator]) -> bool {
+        (0..combinations(&c.values, ops)).any(|i| evaluate(&c.values, ops, i) == Some(c.total))
+    }
+
+    fn calibrations() -> impl Strategy<Value = String> {
+        // totals are either reachable with some combination of operators or arbitrary
+        let calibration = prop::collection::vec(1u64..20, 1..6).prop_flat_map(|values| {
+            let reachable: Vec<u64> = (0..combinations(&values, &ALL))
+                .filter_map(|i| evaluate(&values, &ALL, i))
+                .collect();
+            (
+                prop_oneof![prop::sample::select(reachable), 1u64..1000],
+                Just(values),
+            )
+        });
+
+        prop::collection::vec(calibration, 1..10).prop_map(|calibrations| {
+            calibrations
+                .iter()
+                .map(|(total, values)| {
+                    let values: Vec<String> = values.iter().map(u64::to_string).collect();
+                    format!("{total}: {}", values.join(" "))
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        })
+    }
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         assert_eq!(input.len(), 9);
 
         let exp = Calibration {
@@ -100,17 +173,113 @@ mod tests {
         assert_eq!(input[5], exp)
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("190: 10 19\n3267 81 40 27\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                2,
+                1,
+                "3267 81 40 27",
+                "expected a calibration like 190: 10 19"
+            )
+        );
+
+        let err = input_generator("190: 10 1.9\n").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 9, "1.9", "expected a number"));
+    }
+
+    #[test]
+    fn test_read_input() {
+        let expected = input_generator(EXAMPLE).unwrap();
+        assert_eq!(read_input(EXAMPLE.as_bytes()).unwrap(), expected);
+        let crlf = windows_style(EXAMPLE);
+        assert_eq!(read_input(crlf.as_bytes()).unwrap(), expected);
+
+        let err = read_input("190: 10 19\n3267 81 40 27\n".as_bytes()).unwrap_err();
+        let ReadError::Parse(err) = err else {
+            panic!("expected a parse error, got {err:?}");
+        };
+        assert_eq!(
+            err,
+            input_generator("190: 10 19\n3267 81 40 27").unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 3749);
+    }
+
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 3749);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 11387);
     }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_solve_parallel() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let ops = [Operator::Add, Operator::Mult, Operator::Concat];
+        assert_eq!(
+            solve(&input, &ops, Mode::Parallel),
+            solve(&input, &ops, Mode::Serial)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_solve_matches_naive(text in calibrations()) {
+            let input = input_generator(&text).unwrap();
+            prop_assert_eq!(&read_input(text.as_bytes()).unwrap(), &input);
+            let expected = |ops: &[Operator]| -> u128 {
+                input
+                    .iter()
+                    .filter(|c| possible_naive(c, ops))
+                    .map(|c| u128::from(c.total))
+                    .sum()
+            };
+            prop_assert_eq!(part1(&input), expected(&[Operator::Add, Operator::Mult]));
+            prop_assert_eq!(
+                part2(&input),
+                expected(&ALL)
+            );
+        }
+    }
+
+    #[test]
+    fn test_leading_multiplication() {
+        // multiplying the first value into an empty accumulator must not drop it
+        let input = input_generator("5: 3 5").unwrap();
+        assert_eq!(part1(&input), 0);
+    }
+
+    #[test]
+    fn test_large_values() {
+        let input = input_generator(indoc! {"
+            18446744073709551615: 18446744073709551615
+            18446744073709551615: 18446744073709551615 2
+            18446744073709551615: 1844674407370955161 5
+            16: 4294967296 4294967296 16
+        "})
+        .unwrap();
+        // only the single value and, with concatenation, 1844674407370955161 || 5 reach the total
+        assert_eq!(part1(&input), 18446744073709551615);
+        assert_eq!(part2(&input), 2 * 18446744073709551615);
+
+        // a calibration with no values can't make any total
+        let input = input_generator("0: \n1: 1").unwrap();
+        assert_eq!(part2(&input), 1);
+    }
 }
@@ -1,182 +1,186 @@
+use crate::error::ParseError;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::input::normalize;
+use crate::render::{Image, Rgb};
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
 use std::collections::{HashMap, HashSet};
 
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
-struct Point(i32, i32);
-
-impl Point {
-    fn add(&self, other: &Point) -> Point {
-        Point(self.0 + other.0, self.1 + other.1)
-    }
-
-    fn subtract(&self, other: &Point) -> Point {
-        Point(self.0 - other.0, self.1 - other.1)
-    }
-}
-
-impl From<&MapLocation> for Point {
-    fn from(value: &MapLocation) -> Self {
-        Point(value.0 as i32, value.1 as i32)
-    }
-}
-
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
-struct MapLocation(usize, usize);
-
-impl MapLocation {
-    fn antinode(&self, other: &Self) -> Point {
-        let diff = self.diff(other);
-        Point::from(self).add(&diff)
-    }
-
-    fn diff(&self, other: &Self) -> Point {
-        Point::from(self).subtract(&other.into())
-    }
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    ............
+    ........0...
+    .....0......
+    .......0....
+    ....0.......
+    ......A.....
+    ............
+    ............
+    ........A...
+    .........A..
+    ............
+    ............
+"};
+
+fn antinode(a: Point, b: Point) -> Point {
+    a + (a - b)
 }
 
 #[derive(Default, Clone, Debug)]
-struct AntennaMap {
-    dimensions: (usize, usize),
-    antennas: HashMap<char, HashSet<MapLocation>>,
+pub struct AntennaMap {
+    map: Grid<char>,
+    antennas: HashMap<char, HashSet<Point>>,
 }
 
 impl AntennaMap {
-    fn on_map(&self, point: &Point) -> bool {
-        let (i, j) = (point.0, point.1);
-        let (max_i, max_j) = self.dimensions;
-        i >= 0 && i < max_i as i32 && j >= 0 && j < max_j as i32
-    }
-
-    fn location(&self, point: &Point) -> Option<MapLocation> {
-        if self.on_map(point) {
-            Some(MapLocation(point.0 as usize, point.1 as usize))
-        } else {
-            None
-        }
+    fn on_map(&self, point: Point) -> bool {
+        self.map.contains(point)
     }
 }
 
 #[aoc_generator(day8)]
-fn input_generator(input: &str) -> AntennaMap {
-    let lines: Vec<&str> = input.lines().collect();
-    let mut ant_map = AntennaMap {
-        dimensions: (lines.len(), lines[0].len()),
-        ..Default::default()
-    };
-
-    lines.into_iter().enumerate().for_each(|(i, l)| {
-        l.chars().enumerate().for_each(|(j, c)| match c {
-            '.' => {}
-            _ => {
-                let points = ant_map.antennas.entry(c).or_default();
-                points.insert(MapLocation(i, j));
-            }
-        })
-    });
+pub fn input_generator(input: &str) -> Result<AntennaMap, ParseError> {
+    let input = &normalize(input);
+    let map = Grid::parse(input, |c| {
+        (c == '.' || c.is_ascii_alphanumeric()).then_some(c)
+    })?;
+    let antennas =
+        map.iter()
+            .filter(|(_, &c)| c != '.')
+            .fold(HashMap::new(), |mut acc, (point, &c)| {
+                let points: &mut HashSet<Point> = acc.entry(c).or_default();
+                points.insert(point);
+                acc
+            });
 
-    ant_map
+    Ok(AntennaMap { map, antennas })
 }
 
-#[aoc(day8, part1)]
-fn part1(ant_map: &AntennaMap) -> usize {
+/// Points twice as far from one antenna as from another of the same frequency.
+fn antinodes(ant_map: &AntennaMap) -> HashSet<Point> {
     ant_map
         .antennas
         .values()
         .fold(HashSet::new(), |mut acc, ant_loc| {
-            let locations: Vec<MapLocation> = ant_loc.iter().cloned().collect();
+            let locations: Vec<Point> = ant_loc.iter().cloned().collect();
             let locations = locations.as_slice();
 
             locations.iter().enumerate().for_each(|(i, head)| {
                 let tail = &locations[i + 1..];
                 if !tail.is_empty() {
-                    tail.iter().for_each(|other| {
-                        if let Some(antinode) = ant_map.location(&head.antinode(other)) {
-                            acc.insert(antinode);
-                        }
-                        if let Some(antinode) = ant_map.location(&other.antinode(head)) {
-                            acc.insert(antinode);
-                        }
+                    tail.iter().for_each(|&other| {
+                        [antinode(*head, other), antinode(other, *head)]
+                            .into_iter()
+                            .filter(|point| ant_map.on_map(*point))
+                            .for_each(|point| {
+                                acc.insert(point);
+                            });
                     });
                 }
             });
             acc
         })
-        .len()
 }
 
-#[aoc(day8, part2)]
-fn part2(ant_map: &AntennaMap) -> usize {
+/// Points in line with two antennas of the same frequency.
+fn resonant_antinodes(ant_map: &AntennaMap) -> HashSet<Point> {
     ant_map
         .antennas
         .values()
         .fold(HashSet::new(), |mut acc, ant_loc| {
-            let locations: Vec<MapLocation> = ant_loc.iter().cloned().collect();
+            let locations: Vec<Point> = ant_loc.iter().cloned().collect();
             let locations = locations.as_slice();
 
             locations.iter().enumerate().for_each(|(i, head)| {
                 let tail = &locations[i + 1..];
                 if !tail.is_empty() {
-                    tail.iter().for_each(|other| {
-                        let diff = head.diff(other);
-                        let mut next = Point::from(head);
-                        acc.insert(head.clone());
-                        while let Some(antinode) = ant_map.location(&next.add(&diff)) {
-                            acc.insert(antinode);
-                            next = next.add(&diff);
-                        }
-
-                        let diff = other.diff(head);
-                        let mut next = Point::from(other);
-                        acc.insert(other.clone());
-                        while let Some(antinode) = ant_map.location(&next.add(&diff)) {
-                            acc.insert(antinode);
-                            next = next.add(&diff);
-                        }
+                    tail.iter().for_each(|&other| {
+                        [(*head, *head - other), (other, other - *head)]
+                            .into_iter()
+                            .for_each(|(start, diff)| {
+                                let mut next = start;
+                                while ant_map.on_map(next) {
+                                    acc.insert(next);
+                                    next += diff;
+                                }
+                            });
                     });
                 }
             });
             acc
         })
-        .len()
+}
+
+#[aoc(day8, part1)]
+pub fn part1(ant_map: &AntennaMap) -> usize {
+    antinodes(ant_map).len()
+}
+
+#[aoc(day8, part2)]
+pub fn part2(ant_map: &AntennaMap) -> usize {
+    resonant_antinodes(ant_map).len()
+}
+
+/// The map with each frequency's antennas in its own colour, part 1 antinodes in white and the
+/// extra part 2 antinodes in grey.
+pub fn render(ant_map: &AntennaMap) -> Image {
+    let antinodes = antinodes(ant_map);
+    let resonant = resonant_antinodes(ant_map);
+    Image::from_grid(&ant_map.map, |point, &c| match c {
+        '.' if antinodes.contains(&point) => Rgb::WHITE,
+        '.' if resonant.contains(&point) => Rgb::grey(110),
+        '.' => Rgb::grey(20),
+        frequency => Rgb::palette(frequency as usize),
+    })
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        ............
-        ........0...
-        .....0......
-        .......0....
-        ....0.......
-        ......A.....
-        ............
-        ............
-        ........A...
-        .........A..
-        ............
-        ............
-    "};
+    use crate::input::windows_style;
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         assert_eq!(input.antennas.len(), 2);
-        assert!(input.antennas[&'A'].contains(&MapLocation(8, 8)));
+        assert!(input.antennas[&'A'].contains(&Point::new(8, 8)));
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("..0.\n.#..\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "#", "unexpected character"));
+    }
+
+    #[test]
+    fn test_render() {
+        let image = render(&input_generator(EXAMPLE).unwrap());
+        assert_eq!(image.dimensions(), (12, 12));
+        assert_eq!(image.pixel(8, 1), Some(Rgb::palette('0' as usize)));
+        assert_eq!(image.pixel(6, 5), Some(Rgb::palette('A' as usize)));
+        assert_ne!(Rgb::palette('0' as usize), Rgb::palette('A' as usize));
+        assert_eq!(image.pixel(6, 0), Some(Rgb::WHITE));
+        assert_eq!(image.pixel(0, 0), Some(Rgb::grey(110)));
+        assert_eq!(image.pixel(2, 0), Some(Rgb::grey(20)));
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 14);
     }
 
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 14);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 34);
     }
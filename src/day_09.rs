@@ -1,8 +1,13 @@
+use crate::error::ParseError;
+use crate::input::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::fmt::{Formatter, Write};
 use std::ops::Range;
 use std::{fmt, iter};
 
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = "2333133121414131402";
+
 #[derive(PartialEq, Eq, Hash, Clone, Debug)]
 enum FileBlock {
     FileId(usize),
@@ -42,7 +47,7 @@ impl FilePointer {
 }
 
 #[derive(Default, PartialEq, Eq, Clone, Debug)]
-struct DiskMap {
+pub struct DiskMap {
     blocks: Vec<FileBlock>,
     orig_file_order: Vec<FilePointer>,
 }
@@ -54,39 +59,13 @@ impl fmt::Display for DiskMap {
 }
 
 impl DiskMap {
-    fn first_free(&self, start: usize) -> usize {
-        let mut cursor = start;
-        while self.blocks[cursor] != FileBlock::FreeSpace {
-            cursor += 1;
-        }
-        cursor
-    }
-
-    fn defrag(&mut self) {
-        //advance cursor to first empty block
-        let mut cursor = self.first_free(0);
-
-        //advance end_cursor to last filled block
-        let mut end_cursor = self.blocks.len() - 1;
-        while self.blocks[end_cursor] == FileBlock::FreeSpace {
-            end_cursor -= 1;
-        }
-
-        while cursor <= end_cursor {
-            self.blocks.swap(cursor, end_cursor);
-
-            while self.blocks[cursor] != FileBlock::FreeSpace {
-                cursor += 1;
-            }
-            while self.blocks[end_cursor] == FileBlock::FreeSpace {
-                end_cursor -= 1;
-            }
-        }
+    fn first_free(&self, start: usize) -> Option<usize> {
+        (start..self.blocks.len()).find(|&i| self.blocks[i].is_free_space())
     }
 
     fn find_empty_span(&self, size: usize, max_idx: usize) -> Option<Range<usize>> {
         //advance cursor to first empty block
-        let first = self.first_free(0);
+        let first = self.first_free(0)?;
         let mut range = Range {
             start: first,
             end: first + size,
@@ -97,7 +76,7 @@ impl DiskMap {
                 .iter()
                 .any(|b| !b.is_free_space())
         {
-            range.start = self.first_free(range.start + 1);
+            range.start = self.first_free(range.start + 1)?;
             range.end = range.start + size;
         }
 
@@ -108,24 +87,17 @@ impl DiskMap {
         }
     }
 
-    fn mv_file(&mut self, file: &FilePointer) {
-        if let Some(empty_range) = self.find_empty_span(file.block_size, file.index) {
-            file.range()
-                .zip(empty_range)
-                .for_each(|(file_block_idx, empty_block_idx)| {
-                    self.blocks.swap(file_block_idx, empty_block_idx);
-                });
-        }
-    }
-
-    fn mv_files(&mut self) {
-        self.orig_file_order
-            .clone()
-            .iter()
-            .rev()
-            .for_each(|file_pointer| {
-                self.mv_file(file_pointer);
+    /// Moves `file` to the leftmost span of free space that fits it, returning whether it moved.
+    fn mv_file(&mut self, file: &FilePointer) -> bool {
+        let Some(empty_range) = self.find_empty_span(file.block_size, file.index) else {
+            return false;
+        };
+        file.range()
+            .zip(empty_range)
+            .for_each(|(file_block_idx, empty_block_idx)| {
+                self.blocks.swap(file_block_idx, empty_block_idx);
             });
+        true
     }
 
     fn checksum(&self) -> usize {
@@ -140,75 +112,233 @@ impl DiskMap {
     }
 }
 
+/// Part one's compaction, moving one block from the end of the disk into the first free block
+/// at a time.
+#[derive(Clone, Debug)]
+pub struct Defrag {
+    disk: DiskMap,
+    cursor: usize,
+    end_cursor: usize,
+}
+
+impl Defrag {
+    pub fn new(disk: DiskMap) -> Self {
+        //advance cursor to first empty block, and end_cursor to last filled block
+        let cursors = disk
+            .first_free(0)
+            .zip(disk.blocks.iter().rposition(|b| !b.is_free_space()));
+        // without both there is nothing to move, which the cursors being crossed stands for
+        let (cursor, end_cursor) = cursors.unwrap_or((1, 0));
+        Defrag {
+            disk,
+            cursor,
+            end_cursor,
+        }
+    }
+}
+
+impl Iterator for Defrag {
+    /// The block moved, and where it moved to.
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<(usize, usize)> {
+        if self.cursor > self.end_cursor {
+            return None;
+        }
+
+        let moved = (self.end_cursor, self.cursor);
+        self.disk.blocks.swap(self.cursor, self.end_cursor);
+
+        while self.disk.blocks[self.cursor] != FileBlock::FreeSpace {
+            self.cursor += 1;
+        }
+        while self.disk.blocks[self.end_cursor] == FileBlock::FreeSpace {
+            self.end_cursor -= 1;
+        }
+        Some(moved)
+    }
+}
+
+impl fmt::Display for Defrag {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.disk)
+    }
+}
+
+/// Part two's compaction, moving whole files in order of decreasing id. Files that don't fit
+/// anywhere to their left are skipped rather than stepped over.
+#[derive(Clone, Debug)]
+pub struct MoveFiles {
+    disk: DiskMap,
+    remaining: Vec<FilePointer>,
+}
+
+impl MoveFiles {
+    pub fn new(disk: DiskMap) -> Self {
+        let remaining = disk.orig_file_order.clone();
+        MoveFiles { disk, remaining }
+    }
+}
+
+impl Iterator for MoveFiles {
+    /// The id of the file moved.
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while let Some(file) = self.remaining.pop() {
+            if self.disk.mv_file(&file) {
+                return Some(file.id);
+            }
+        }
+        None
+    }
+}
+
+impl fmt::Display for MoveFiles {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.disk)
+    }
+}
+
 #[aoc_generator(day9)]
-fn input_generator(input: &str) -> DiskMap {
+pub fn input_generator(input: &str) -> Result<DiskMap, ParseError> {
+    let input = &normalize(input);
+    let mut lines = input.lines().enumerate();
+    let line = lines.next().map_or("", |(_, line)| line);
+    if let Some((line_idx, extra)) = lines.next() {
+        return Err(ParseError::at(
+            line_idx,
+            extra,
+            extra,
+            "expected the disk map on one line",
+        ));
+    }
+
     let mut files = vec![];
-    let blocks: Vec<FileBlock> = input.chars().enumerate().fold(vec![], |mut acc, (i, c)| {
-        let x = c.to_string().parse().unwrap();
-
-        let block = if i % 2 == 0 {
-            // even index is a file id
-            let file_id = i / 2;
-
-            //record in files
-            files.push(FilePointer {
-                id: file_id,
-                index: acc.len(),
-                block_size: x,
-            });
+    let blocks: Vec<FileBlock> =
+        line.char_indices()
+            .enumerate()
+            .try_fold(vec![], |mut acc, (i, (offset, c))| {
+                let x = c.to_digit(10).ok_or_else(|| {
+                    let text = &line[offset..offset + c.len_utf8()];
+                    ParseError::at(0, line, text, "expected a digit")
+                })? as usize;
 
-            FileBlock::FileId(file_id)
-        } else {
-            FileBlock::FreeSpace
-        };
+                let block = if i % 2 == 0 {
+                    // even index is a file id
+                    let file_id = i / 2;
+
+                    //record in files
+                    files.push(FilePointer {
+                        id: file_id,
+                        index: acc.len(),
+                        block_size: x,
+                    });
 
-        acc.extend(iter::repeat(block).take(x));
-        acc
-    });
+                    FileBlock::FileId(file_id)
+                } else {
+                    FileBlock::FreeSpace
+                };
 
-    DiskMap {
+                acc.extend(iter::repeat_n(block, x));
+                Ok(acc)
+            })?;
+
+    Ok(DiskMap {
         blocks,
         orig_file_order: files,
-    }
+    })
 }
 
 #[aoc(day9, part1)]
-fn part1(disk: &DiskMap) -> usize {
-    let mut disk = disk.clone();
-    disk.defrag();
-    disk.checksum()
+pub fn part1(disk: &DiskMap) -> usize {
+    let mut defrag = Defrag::new(disk.clone());
+    defrag.by_ref().for_each(drop);
+    defrag.disk.checksum()
 }
 
 #[aoc(day9, part2)]
-fn part2(disk: &DiskMap) -> usize {
-    let mut disk = disk.clone();
-    disk.mv_files();
-    disk.checksum()
+pub fn part2(disk: &DiskMap) -> usize {
+    let mut moves = MoveFiles::new(disk.clone());
+    moves.by_ref().for_each(drop);
+    moves.disk.checksum()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TEST_INPUT: &str = "2333133121414131402";
+    use crate::input::windows_style;
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let exp = String::from("00...111...2...333.44.5555.6666.777.888899");
         assert_eq!(input.to_string(), exp);
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("23331 33121").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 6, " ", "expected a digit"));
+
+        let err = input_generator("2333\n133").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "133", "expected the disk map on one line")
+        );
+    }
+
+    #[test]
+    fn test_no_free_space() {
+        for input in ["", "0", "6", "60"] {
+            let disk = input_generator(input).unwrap();
+            assert_eq!(part1(&disk), 0);
+            assert_eq!(part2(&disk), 0);
+        }
+
+        let disk = input_generator("203").unwrap();
+        assert_eq!(part1(&disk), 9);
+        assert_eq!(part2(&disk), 9);
+    }
+
+    #[test]
+    fn test_defrag_steps() {
+        let mut defrag = Defrag::new(input_generator(EXAMPLE).unwrap());
+        assert_eq!(defrag.next(), Some((41, 2)));
+        assert_eq!(
+            defrag.to_string(),
+            "009..111...2...333.44.5555.6666.777.88889.\n"
+        );
+        assert_eq!(defrag.last(), Some((28, 26)));
+    }
+
+    #[test]
+    fn test_move_files_steps() {
+        let mut moves = MoveFiles::new(input_generator(EXAMPLE).unwrap());
+        assert_eq!(moves.next(), Some(9));
+        assert_eq!(
+            moves.to_string(),
+            "0099.111...2...333.44.5555.6666.777.8888..\n"
+        );
+        assert_eq!(moves.collect::<Vec<_>>(), [7, 4, 2]);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 1928);
+    }
+
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 1928);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 2858);
     }
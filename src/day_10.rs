@@ -1,146 +1,202 @@
+use crate::error::ParseError;
+use crate::export::Graph;
+use crate::geom::Point;
+use crate::grid::Grid;
+use crate::input::normalize;
+use crate::render::{Image, Rgb};
 use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
 use std::collections::{HashMap, HashSet};
 
-const DIRECTIONS: &[(i32, i32); 4] = &[(0, 1), (0, -1), (1, 0), (-1, 0)];
-
-#[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
-struct Point(i32, i32);
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    89010123
+    78121874
+    87430965
+    96549874
+    45678903
+    32019012
+    01329801
+    10456732
+"};
 
 #[derive(Default, Clone, Debug)]
-struct TgMap(Vec<Vec<u8>>);
+pub struct TgMap(Grid<u8>);
 
 impl TgMap {
-    fn on_map(&self, point: &Point) -> bool {
-        let (i, j) = (point.0, point.1);
-        let (max_i, max_j) = self.dimensions();
-        i >= 0 && i < max_i as i32 && j >= 0 && j < max_j as i32
-    }
-
-    fn dimensions(&self) -> (usize, usize) {
-        (self.0.len(), self.0[0].len())
-    }
-
     fn trailheads(&self) -> Vec<Point> {
-        self.0
-            .iter()
-            .enumerate()
-            .flat_map(|(i, row)| {
-                row.iter().enumerate().filter_map(move |(j, &cell)| {
-                    if cell == 0 {
-                        Some(Point(i as i32, j as i32))
-                    } else {
-                        None
-                    }
-                })
-            })
-            .collect()
+        self.0.positions(|&height| height == 0).collect()
     }
 
-    fn at(&self, pos: &Point) -> u8 {
-        self.0[pos.0 as usize][pos.1 as usize]
+    fn at(&self, pos: Point) -> u8 {
+        self.0.get(pos).cloned().unwrap()
     }
 
-    fn find_nines(&self, pos: &Point) -> HashSet<Point> {
+    fn uphill(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
         let val = self.at(pos);
+        self.0
+            .neighbors4(pos)
+            .filter(move |neighbor| self.at(*neighbor) == val + 1)
+    }
 
-        if val == 9 {
-            return HashSet::from([pos.clone()]);
+    fn find_nines(&self, pos: Point) -> HashSet<Point> {
+        if self.at(pos) == 9 {
+            return HashSet::from([pos]);
         }
 
-        DIRECTIONS
-            .iter()
-            .fold(HashSet::new(), |mut acc, (idx, jdx)| {
-                let neighbor = Point(pos.0 + idx, pos.1 + jdx);
-                if self.on_map(&neighbor) && self.at(&neighbor) == val + 1 {
-                    acc.extend(self.find_nines(&neighbor));
-                }
-                acc
-            })
+        self.uphill(pos).fold(HashSet::new(), |mut acc, neighbor| {
+            acc.extend(self.find_nines(neighbor));
+            acc
+        })
     }
 
     fn scores(&self) -> HashMap<Point, usize> {
         self.trailheads()
-            .iter()
-            .map(|th| (th.clone(), self.find_nines(th).len()))
+            .into_iter()
+            .map(|th| (th, self.find_nines(th).len()))
             .collect()
     }
 
-    fn rating(&self, pos: &Point) -> usize {
-        let val = self.at(pos);
-        if val == 9 {
+    fn rating(&self, pos: Point) -> usize {
+        if self.at(pos) == 9 {
             return 1;
         }
 
-        DIRECTIONS.iter().fold(0, |acc, (idx, jdx)| {
-            let neighbor = Point(pos.0 + idx, pos.1 + jdx);
-            if self.on_map(&neighbor) && self.at(&neighbor) == val + 1 {
-                acc + self.rating(&neighbor)
-            } else {
-                acc
-            }
-        })
+        self.uphill(pos).map(|neighbor| self.rating(neighbor)).sum()
+    }
+
+    /// Adds `pos` and every point above it on a trail to `cells`, returning whether any trail
+    /// from `pos` reaches a 9.
+    fn climb(&self, pos: Point, cells: &mut HashSet<Point>) -> bool {
+        // every neighbour is climbed, not just up to the first that reaches a 9
+        let reaching = self
+            .uphill(pos)
+            .filter(|&neighbor| self.climb(neighbor, cells))
+            .count();
+        let reaches_nine = self.at(pos) == 9 || reaching > 0;
+        if reaches_nine {
+            cells.insert(pos);
+        }
+        reaches_nine
+    }
+
+    /// Every point on a trail from a trailhead to a 9.
+    fn trail_cells(&self) -> HashSet<Point> {
+        let mut cells = HashSet::new();
+        self.trailheads().into_iter().for_each(|th| {
+            self.climb(th, &mut cells);
+        });
+        cells
     }
 
     fn ratings(&self) -> HashMap<Point, usize> {
         self.trailheads()
-            .iter()
-            .map(|th| (th.clone(), self.rating(th)))
+            .into_iter()
+            .map(|th| (th, self.rating(th)))
             .collect()
     }
 }
 
 #[aoc_generator(day10)]
-fn input_generator(input: &str) -> TgMap {
-    let map: Vec<Vec<u8>> = input
-        .lines()
-        .map(|l| l.chars().map(|c| c.to_digit(10).unwrap() as u8).collect())
-        .collect();
-    TgMap(map)
+pub fn input_generator(input: &str) -> Result<TgMap, ParseError> {
+    let input = &normalize(input);
+    let map = Grid::parse(input, |c| c.to_digit(10).map(|d| d as u8))?;
+    Ok(TgMap(map))
 }
 
 #[aoc(day10, part1)]
-fn part1(tg_map: &TgMap) -> usize {
+pub fn part1(tg_map: &TgMap) -> usize {
     tg_map.scores().values().sum()
 }
 
 #[aoc(day10, part2)]
-fn part2(tg_map: &TgMap) -> usize {
+pub fn part2(tg_map: &TgMap) -> usize {
     tg_map.ratings().values().sum()
 }
 
+/// The map in shades of grey from low to high, with the points on trails in shades of green.
+pub fn render(tg_map: &TgMap) -> Image {
+    let trails = tg_map.trail_cells();
+    Image::from_grid(&tg_map.0, |point, &height| {
+        let shade = 40 + height * 23;
+        match trails.contains(&point) {
+            true => Rgb(0, shade, 0),
+            false => Rgb::grey(shade),
+        }
+    })
+}
+
+/// The trails as a graph with an edge for each step up, laid out like the map. With a
+/// `trailhead`, the index of one in reading order, every trail from it is highlighted.
+pub fn trail_graph(tg_map: &TgMap, trailhead: Option<usize>) -> Graph {
+    let mut cells: Vec<Point> = tg_map.trail_cells().into_iter().collect();
+    // reading order
+    cells.sort_by_key(|p| (p.y, p.x));
+
+    let mut highlighted = HashSet::new();
+    if let Some(th) = trailhead.and_then(|i| tg_map.trailheads().get(i).copied()) {
+        tg_map.climb(th, &mut highlighted);
+    }
+
+    let id = |p: &Point| format!("{},{}", p.x, p.y);
+    let mut graph = Graph::new("trails");
+    cells.iter().for_each(|p| {
+        let height = tg_map.at(*p).to_string();
+        graph.add_node_at(&id(p), &height, *p, highlighted.contains(p));
+    });
+    cells.iter().for_each(|p| {
+        tg_map.uphill(*p).for_each(|up| {
+            // a step up from a trail cell may lead to a dead end
+            let highlight = highlighted.contains(p) && highlighted.contains(&up);
+            graph.add_edge(&id(p), &id(&up), highlight);
+        });
+    });
+    graph
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use indoc::indoc;
-
-    const TEST_INPUT: &str = indoc! {"
-        89010123
-        78121874
-        87430965
-        96549874
-        45678903
-        32019012
-        01329801
-        10456732
-    "};
+    use crate::input::windows_style;
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
-        assert_eq!(input.0.len(), 8);
-        assert_eq!(input.0[0].len(), 8);
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.0.dimensions(), (8, 8));
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("0123\n1234\n23a5\n").unwrap_err();
+        assert_eq!(err, ParseError::new(3, 3, "a", "unexpected character"));
+    }
+
+    #[test]
+    fn test_render() {
+        let image = render(&input_generator(EXAMPLE).unwrap());
+        assert_eq!(image.dimensions(), (8, 8));
+        // the trailhead at (2, 0) and a 9 it reaches at (1, 0), then an 8 no trail passes through
+        assert_eq!(image.pixel(2, 0), Some(Rgb(0, 40, 0)));
+        assert_eq!(image.pixel(1, 0), Some(Rgb(0, 247, 0)));
+        assert_eq!(image.pixel(0, 0), Some(Rgb::grey(224)));
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 36);
     }
 
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 36);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 81);
     }
@@ -1,8 +1,13 @@
+use crate::error::{parse_number, ParseError};
+use crate::input::normalize;
 use aoc_runner_derive::{aoc, aoc_generator};
 use std::collections::HashMap;
 
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = "125 17";
+
 #[derive(Default, PartialEq, Eq, Hash, Clone, Debug)]
-struct Stone(u64);
+pub struct Stone(u64);
 
 impl Stone {
     fn transform(&self) -> Vec<Stone> {
@@ -13,7 +18,7 @@ impl Stone {
         }
 
         let digits = val.to_string();
-        if digits.len() % 2 == 0 {
+        if digits.len().is_multiple_of(2) {
             let digits = digits.as_str();
             let mid_idx = digits.len() / 2;
             return vec![
@@ -51,22 +56,40 @@ impl TransformCache {
     }
 }
 
+/// Stones with more digits than this could outgrow a `u64` while blinking: an odd number of
+/// digits `n` grows to at most `n + 7` before splitting.
+const MAX_DIGITS: usize = 12;
+
 #[aoc_generator(day11)]
-fn input_generator(input: &str) -> Vec<Stone> {
+pub fn input_generator(input: &str) -> Result<Vec<Stone>, ParseError> {
+    let input = &normalize(input);
     input
-        .split_whitespace()
-        .map(|n| Stone(n.parse().unwrap()))
+        .lines()
+        .enumerate()
+        .flat_map(|(i, l)| l.split_whitespace().map(move |n| (i, l, n)))
+        .map(|(i, l, n)| {
+            let stone: u64 = parse_number(i, l, n)?;
+            if stone.to_string().len() > MAX_DIGITS {
+                return Err(ParseError::at(
+                    i,
+                    l,
+                    n,
+                    "expected a stone of at most 12 digits",
+                ));
+            }
+            Ok(Stone(stone))
+        })
         .collect()
 }
 
 #[aoc(day11, part1)]
-fn part1(stones: &[Stone]) -> usize {
+pub fn part1(stones: &[Stone]) -> usize {
     let mut cache = TransformCache::default();
     stones.iter().map(|s| cache.blink_times(25, s)).sum()
 }
 
 #[aoc(day11, part2)]
-fn part2(stones: &[Stone]) -> usize {
+pub fn part2(stones: &[Stone]) -> usize {
     let mut cache = TransformCache::default();
     stones.iter().map(|s| cache.blink_times(75, s)).sum()
 }
@@ -74,26 +97,78 @@ fn part2(stones: &[Stone]) -> usize {
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    const TEST_INPUT: &str = "125 17";
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    /// Expands the row of stones literally, one blink at a time.
+    fn blink_naive(stones: &[Stone], blinks: usize) -> usize {
+        (0..blinks)
+            .fold(stones.to_vec(), |row, _| {
+                row.iter().flat_map(Stone::transform).collect()
+            })
+            .len()
+    }
 
     #[test]
     fn test_input_generator() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         assert_eq!(input, vec![Stone(125), Stone(17)]);
     }
 
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("125 17,").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 5, "17,", "expected a number"));
+
+        let err = input_generator("125 1000000000000").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                1,
+                5,
+                "1000000000000",
+                "expected a stone of at most 12 digits"
+            )
+        );
+    }
+
+    #[test]
+    fn test_large_stones() {
+        let input = input_generator("999999999999 99999999999 0").unwrap();
+        assert!(part2(&input) > 0);
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 55312);
+    }
+
     #[test]
     fn test_part_one() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part1(&input);
         assert_eq!(result, 55312);
     }
 
     #[test]
     fn test_part_two() {
-        let input = input_generator(TEST_INPUT);
+        let input = input_generator(EXAMPLE).unwrap();
         let result = part2(&input);
         assert_eq!(result, 65601038650482);
     }
+
+    proptest! {
+        #[test]
+        fn test_blink_times_matches_naive(
+            stones in prop::collection::vec(0u64..10_000, 1..5),
+            blinks in 1usize..15,
+        ) {
+            let text: Vec<String> = stones.iter().map(u64::to_string).collect();
+            let input = input_generator(&text.join(" ")).unwrap();
+            let mut cache = TransformCache::default();
+            let result: usize = input.iter().map(|s| cache.blink_times(blinks, s)).sum();
+            prop_assert_eq!(result, blink_naive(&input, blinks));
+        }
+    }
 }
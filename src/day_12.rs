@@ -0,0 +1,270 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    RRRRIICCFF
+    RRRRIICCCF
+    VVRRRCCFFF
+    VVRCCCJFFF
+    VVVVCJJCFE
+    VVIVCCJJEE
+    VVIIICJJEE
+    MIIIIIJJEE
+    MIIISIJEEE
+    MMMISSJEEE
+"};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Garden {
+    plots: Grid<char>,
+}
+
+/// A connected group of plots growing the same plant.
+#[derive(Debug, PartialEq, Clone)]
+struct Region {
+    plant: char,
+    plots: Vec<Point>,
+}
+
+impl Garden {
+    pub fn new(input: &str) -> Result<Self, ParseError> {
+        let plots = Grid::parse(input, |c| c.is_ascii_uppercase().then_some(c))?;
+        Ok(Garden { plots })
+    }
+
+    /// Whether the plot at `pos` grows `plant`, which is never the case outside the garden.
+    fn grows(&self, pos: Point, plant: char) -> bool {
+        self.plots.get(pos) == Some(&plant)
+    }
+
+    /// Every region, labelled by flood fill in reading order of their first plot.
+    fn regions(&self) -> Vec<Region> {
+        let mut seen = self.plots.map(|_| false);
+        let mut regions = vec![];
+
+        for (start, &plant) in self.plots.iter() {
+            if seen.get(start) == Some(&true) {
+                continue;
+            }
+            *seen.get_mut(start).unwrap() = true;
+
+            let mut plots = vec![];
+            let mut stack = vec![start];
+            while let Some(pos) = stack.pop() {
+                plots.push(pos);
+                self.plots.neighbors4(pos).for_each(|next| {
+                    let visited = seen.get_mut(next).unwrap();
+                    if !*visited && self.grows(next, plant) {
+                        *visited = true;
+                        stack.push(next);
+                    }
+                });
+            }
+            regions.push(Region { plant, plots });
+        }
+
+        regions
+    }
+
+    /// The fence segments around `region`, one for each side of a plot not facing the region.
+    fn perimeter(&self, region: &Region) -> usize {
+        region
+            .plots
+            .iter()
+            .map(|&pos| {
+                Direction::ORTHOGONAL
+                    .iter()
+                    .filter(|&&d| !self.grows(pos + d, region.plant))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The straight sides of `region`'s fence, which are as many as its corners.
+    fn sides(&self, region: &Region) -> usize {
+        region
+            .plots
+            .iter()
+            .map(|&pos| {
+                Direction::ORTHOGONAL
+                    .iter()
+                    .filter(|&&d| {
+                        let cw = d.rotate_cw();
+                        let along = self.grows(pos + d, region.plant);
+                        let across = self.grows(pos + cw, region.plant);
+                        let diagonal = self.grows(pos + d + cw, region.plant);
+                        // an outside corner, or an inside one where the region wraps around
+                        (!along && !across) || (along && across && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    fn price(&self, fence: impl Fn(&Self, &Region) -> usize) -> usize {
+        self.regions()
+            .iter()
+            .map(|region| region.plots.len() * fence(self, region))
+            .sum()
+    }
+}
+
+#[aoc_generator(day12)]
+pub fn input_generator(input: &str) -> Result<Garden, ParseError> {
+    let input = &normalize(input);
+    Garden::new(input)
+}
+
+#[aoc(day12, part1)]
+pub fn part1(garden: &Garden) -> usize {
+    garden.price(Garden::perimeter)
+}
+
+#[aoc(day12, part2)]
+pub fn part2(garden: &Garden) -> usize {
+    garden.price(Garden::sides)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    const SMALL: &str = indoc! {"
+        AAAA
+        BBCD
+        BBCC
+        EEEC
+    "};
+
+    const ENCLOSED: &str = indoc! {"
+        OOOOO
+        OXOXO
+        OOOOO
+        OXOXO
+        OOOOO
+    "};
+
+    const E_SHAPED: &str = indoc! {"
+        EEEEE
+        EXXXX
+        EEEEE
+        EXXXX
+        EEEEE
+    "};
+
+    const DIAGONAL: &str = indoc! {"
+        AAAAAA
+        AAABBA
+        AAABBA
+        ABBAAA
+        ABBAAA
+        AAAAAA
+    "};
+
+    /// Counts each side once, at the fence segment that starts it going clockwise.
+    fn sides_naive(garden: &Garden, region: &Region) -> usize {
+        let fenced = |pos: Point, d: Direction| {
+            garden.grows(pos, region.plant) && !garden.grows(pos + d, region.plant)
+        };
+        region
+            .plots
+            .iter()
+            .map(|&pos| {
+                Direction::ORTHOGONAL
+                    .iter()
+                    .filter(|&&d| fenced(pos, d) && !fenced(pos + d.rotate_ccw(), d))
+                    .count()
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_input_generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.plots.dimensions(), (10, 10));
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("AAAA\nBBcD\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "c", "unexpected character"));
+
+        let err = input_generator("AAAA\nBBC\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "BBC", "expected a row of 4 cells, found 3")
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 1930);
+    }
+
+    #[test]
+    fn test_regions() {
+        let garden = input_generator(SMALL).unwrap();
+        let regions = garden.regions();
+        let plants: Vec<char> = regions.iter().map(|r| r.plant).collect();
+        assert_eq!(plants, vec!['A', 'B', 'C', 'D', 'E']);
+
+        let fences: Vec<(usize, usize, usize)> = regions
+            .iter()
+            .map(|r| (r.plots.len(), garden.perimeter(r), garden.sides(r)))
+            .collect();
+        assert_eq!(
+            fences,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+
+        // the two X regions inside the O region are separate regions of the same plant
+        let garden = input_generator(ENCLOSED).unwrap();
+        assert_eq!(garden.regions().len(), 5);
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part1(&input_generator(SMALL).unwrap()), 140);
+        assert_eq!(part1(&input_generator(ENCLOSED).unwrap()), 772);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part1(&input);
+        assert_eq!(result, 1930);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part2(&input_generator(SMALL).unwrap()), 80);
+        assert_eq!(part2(&input_generator(ENCLOSED).unwrap()), 436);
+        assert_eq!(part2(&input_generator(E_SHAPED).unwrap()), 236);
+        assert_eq!(part2(&input_generator(DIAGONAL).unwrap()), 368);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part2(&input);
+        assert_eq!(result, 1206);
+    }
+
+    #[test]
+    fn test_empty() {
+        let input = input_generator("").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (0, 0));
+    }
+
+    proptest! {
+        #[test]
+        fn test_sides_match_naive(rows in prop::collection::vec("[ABC]{6}", 1..7)) {
+            let garden = input_generator(&rows.join("\n")).unwrap();
+            garden.regions().iter().for_each(|region| {
+                assert_eq!(garden.sides(region), sides_naive(&garden, region));
+            });
+        }
+    }
+}
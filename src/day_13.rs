@@ -0,0 +1,318 @@
+use crate::error::{parse_number, ParseError};
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    Button A: X+94, Y+34
+    Button B: X+22, Y+67
+    Prize: X=8400, Y=5400
+
+    Button A: X+26, Y+66
+    Button B: X+67, Y+21
+    Prize: X=12748, Y=12176
+
+    Button A: X+17, Y+86
+    Button B: X+84, Y+37
+    Prize: X=7870, Y=6450
+
+    Button A: X+69, Y+23
+    Button B: X+27, Y+71
+    Prize: X=18641, Y=10279
+"};
+
+/// How far part two's prizes really are, along both axes.
+const OFFSET: i64 = 10_000_000_000_000;
+
+/// Each line of a machine: the text before its X value, between its X and Y values, and an
+/// example for error messages.
+const LINES: [(&str, &str, &str); 3] = [
+    ("Button A: X+", ", Y+", "Button A: X+94, Y+34"),
+    ("Button B: X+", ", Y+", "Button B: X+22, Y+67"),
+    ("Prize: X=", ", Y=", "Prize: X=8400, Y=5400"),
+];
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Machine {
+    a: (i64, i64),
+    b: (i64, i64),
+    prize: (i64, i64),
+}
+
+impl Machine {
+    /// The presses of A and B that reach the prize moved by `offset`, if there is exactly one
+    /// way to reach it with whole, non-negative presses.
+    fn presses(&self, offset: i64) -> Option<(i128, i128)> {
+        // the input values fit in a u32, so none of these products come near an i128's limits
+        let (ax, ay) = (i128::from(self.a.0), i128::from(self.a.1));
+        let (bx, by) = (i128::from(self.b.0), i128::from(self.b.1));
+        let px = i128::from(self.prize.0) + i128::from(offset);
+        let py = i128::from(self.prize.1) + i128::from(offset);
+
+        // Cramer's rule; buttons moving the same way give no solution or a line of them, which
+        // the puzzle's machines never do
+        let det = ax * by - ay * bx;
+        if det == 0 {
+            return None;
+        }
+        let a_num = px * by - py * bx;
+        let b_num = ax * py - ay * px;
+        if a_num % det != 0 || b_num % det != 0 {
+            return None;
+        }
+
+        let (a, b) = (a_num / det, b_num / det);
+        (a >= 0 && b >= 0).then_some((a, b))
+    }
+}
+
+/// The tokens spent on A's 3 and B's 1 per press.
+fn tokens((a, b): (i128, i128)) -> i128 {
+    3 * a + b
+}
+
+fn parse_line(i: usize, line: &str, spec: (&str, &str, &str)) -> Result<(i64, i64), ParseError> {
+    let (prefix, separator, example) = spec;
+    let malformed = || ParseError::at(i, line, line, &format!("expected a line like {example}"));
+    let (x, y) = line
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.split_once(separator))
+        .ok_or_else(malformed)?;
+    let x: u32 = parse_number(i, line, x)?;
+    let y: u32 = parse_number(i, line, y)?;
+    Ok((i64::from(x), i64::from(y)))
+}
+
+/// Parses the machine in `block`, whose first line is the zero-based `offset`-th of the input.
+fn parse_machine(offset: usize, block: &str) -> Result<Machine, ParseError> {
+    let mut lines = block.lines().enumerate().map(|(i, l)| (offset + i, l));
+    let mut values = LINES.iter().map(|&spec| match lines.next() {
+        Some((i, line)) => parse_line(i, line, spec),
+        None => Err(ParseError::new(
+            offset + block.lines().count() + 1,
+            1,
+            "",
+            &format!("expected a line like {}", spec.2),
+        )),
+    });
+    let machine = Machine {
+        a: values.next().unwrap()?,
+        b: values.next().unwrap()?,
+        prize: values.next().unwrap()?,
+    };
+
+    match lines.next() {
+        Some((i, line)) => Err(ParseError::at(
+            i,
+            line,
+            line,
+            "expected a blank line between machines",
+        )),
+        None => Ok(machine),
+    }
+}
+
+#[aoc_generator(day13)]
+pub fn input_generator(input: &str) -> Result<Vec<Machine>, ParseError> {
+    let input = &normalize(input);
+    if input.is_empty() {
+        return Ok(vec![]);
+    }
+
+    let mut offset = 0;
+    input
+        .split("\n\n")
+        .map(|block| {
+            let machine = parse_machine(offset, block);
+            // the next machine starts after this one and the blank line
+            offset += block.lines().count() + 1;
+            machine
+        })
+        .collect()
+}
+
+#[aoc(day13, part1)]
+pub fn part1(machines: &[Machine]) -> i128 {
+    machines
+        .iter()
+        .filter_map(|m| m.presses(0))
+        // no button is pressed more than 100 times
+        .filter(|&(a, b)| a <= 100 && b <= 100)
+        .map(tokens)
+        .sum()
+}
+
+#[aoc(day13, part2)]
+pub fn part2(machines: &[Machine]) -> i128 {
+    machines
+        .iter()
+        .filter_map(|m| m.presses(OFFSET))
+        .map(tokens)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    /// The cheapest way to win with at most 100 presses of each button, trying every one.
+    fn tokens_naive(m: &Machine) -> Option<i128> {
+        (0..=100)
+            .flat_map(|a| (0..=100).map(move |b| (a, b)))
+            .filter(|&(a, b)| {
+                a * m.a.0 + b * m.b.0 == m.prize.0 && a * m.a.1 + b * m.b.1 == m.prize.1
+            })
+            .map(|(a, b)| tokens((i128::from(a), i128::from(b))))
+            .min()
+    }
+
+    fn machines() -> impl Strategy<Value = Vec<Machine>> {
+        // prizes are either reachable with a few presses or arbitrary
+        let machine = (
+            (1i64..30, 1i64..30),
+            (1i64..30, 1i64..30),
+            0i64..50,
+            0i64..50,
+        )
+            .prop_flat_map(|(a, b, a_presses, b_presses)| {
+                let reachable = (
+                    a_presses * a.0 + b_presses * b.0,
+                    a_presses * a.1 + b_presses * b.1,
+                );
+                let prize = prop_oneof![Just(reachable), (0i64..3000, 0i64..3000)];
+                (Just(a), Just(b), prize)
+            })
+            .prop_map(|(a, b, prize)| Machine { a, b, prize })
+            // buttons moving the same way are never in the puzzle's input
+            .prop_filter("collinear buttons", |m| m.a.0 * m.b.1 != m.a.1 * m.b.0);
+        prop::collection::vec(machine, 1..8)
+    }
+
+    #[test]
+    fn test_input_generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.len(), 4);
+        assert_eq!(
+            input[1],
+            Machine {
+                a: (26, 66),
+                b: (67, 21),
+                prize: (12748, 12176),
+            }
+        );
+        assert!(input_generator("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("Button A: X+94, Y+34\nButton B: X+22 Y+67\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                2,
+                1,
+                "Button B: X+22 Y+67",
+                "expected a line like Button B: X+22, Y+67"
+            )
+        );
+
+        let input = EXAMPLE.replace("X=7870", "X=-7870");
+        let err = input_generator(&input).unwrap_err();
+        assert_eq!(err, ParseError::new(11, 10, "-7870", "expected a number"));
+
+        let err = input_generator("Button A: X+94, Y+34\nButton B: X+22, Y+67\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(3, 1, "", "expected a line like Prize: X=8400, Y=5400")
+        );
+
+        let input = EXAMPLE.replacen("\n\n", "\n", 1);
+        let err = input_generator(&input).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                4,
+                1,
+                "Button A: X+26, Y+66",
+                "expected a blank line between machines"
+            )
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 480);
+    }
+
+    #[test]
+    fn test_presses() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input[0].presses(0), Some((80, 40)));
+        assert_eq!(input[1].presses(0), None);
+        assert_eq!(input[2].presses(0), Some((38, 86)));
+        assert_eq!(input[3].presses(0), None);
+
+        // only the second and fourth machines can be won once the prizes move
+        let won: Vec<bool> = input.iter().map(|m| m.presses(OFFSET).is_some()).collect();
+        assert_eq!(won, vec![false, true, false, true]);
+
+        // a prize behind the claw would take negative presses
+        let machine = Machine {
+            a: (1, 0),
+            b: (0, 1),
+            prize: (0, 0),
+        };
+        assert_eq!(machine.presses(-1), None);
+        assert_eq!(machine.presses(0), Some((0, 0)));
+    }
+
+    #[test]
+    fn test_collinear_buttons() {
+        // the prize is on the buttons' line, but with many ways to reach it these are skipped
+        let machine = Machine {
+            a: (2, 4),
+            b: (1, 2),
+            prize: (4, 8),
+        };
+        assert_eq!(machine.presses(0), None);
+    }
+
+    #[test]
+    fn test_large_values() {
+        let input = input_generator(indoc! {"
+            Button A: X+4294967295, Y+1
+            Button B: X+1, Y+4294967295
+            Prize: X=4294967295, Y=4294967295
+        "})
+        .unwrap();
+        // both buttons would need pressing 4294967295/4294967296 times, with no overflow on the way
+        assert_eq!(part1(&input), 0);
+        assert_eq!(part2(&input), 0);
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part1(&input);
+        assert_eq!(result, 480);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part2(&input);
+        assert_eq!(result, 875318608908);
+    }
+
+    proptest! {
+        #[test]
+        fn test_part_one_matches_naive(machines in machines()) {
+            let expected: i128 = machines.iter().filter_map(tokens_naive).sum();
+            prop_assert_eq!(part1(&machines), expected);
+        }
+    }
+}
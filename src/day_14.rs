@@ -0,0 +1,393 @@
+use crate::error::{parse_number, ParseError};
+use crate::geom::Point;
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+use regex::Regex;
+use std::fmt::{self, Formatter};
+
+/// The example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    p=0,4 v=3,-3
+    p=6,3 v=-1,-3
+    p=10,3 v=-1,2
+    p=2,0 v=2,-1
+    p=0,0 v=1,3
+    p=3,0 v=-2,-2
+    p=7,6 v=-1,-3
+    p=3,0 v=-1,-2
+    p=9,3 v=2,3
+    p=7,3 v=-1,2
+    p=2,4 v=2,-3
+    p=9,5 v=-3,-3
+"};
+
+/// The width and height of the real lobby.
+pub const LOBBY: (i32, i32) = (101, 103);
+
+/// The width and height of the example's smaller lobby.
+pub const EXAMPLE_LOBBY: (i32, i32) = (11, 7);
+
+/// How long part one's robots move for.
+const SECONDS: usize = 100;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Robot {
+    pos: Point,
+    vel: Point,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Lobby {
+    width: i32,
+    height: i32,
+    robots: Vec<Robot>,
+}
+
+impl Lobby {
+    /// `robots` in a lobby `width` by `height` tiles, or `None` unless both are positive.
+    pub fn new(robots: Vec<Robot>, (width, height): (i32, i32)) -> Option<Self> {
+        (width > 0 && height > 0).then_some(Lobby {
+            width,
+            height,
+            robots,
+        })
+    }
+
+    /// Where each robot is after `seconds`, teleporting across the edges of the lobby.
+    fn positions(&self, seconds: usize) -> impl Iterator<Item = Point> + '_ {
+        let seconds = seconds as i64;
+        let wrap = move |p: i32, v: i32, size: i32| {
+            // with i64 a velocity of any i32 can move for any number of seconds in a period
+            (i64::from(p) + i64::from(v) * seconds).rem_euclid(i64::from(size)) as i32
+        };
+        self.robots.iter().map(move |r| {
+            Point::new(
+                wrap(r.pos.x, r.vel.x, self.width),
+                wrap(r.pos.y, r.vel.y, self.height),
+            )
+        })
+    }
+
+    /// The product of the robot counts in each quadrant after `seconds`, leaving out robots on
+    /// the middle row or column.
+    fn safety_factor(&self, seconds: usize) -> usize {
+        let mut quadrants = [0; 4];
+        self.positions(seconds).for_each(|p| {
+            // doubled, the middle of an odd size is the size itself
+            let half = |c: i32, size: i32| (2 * c + 1).cmp(&size);
+            match (half(p.x, self.width), half(p.y, self.height)) {
+                (x, y) if x.is_eq() || y.is_eq() => {}
+                (x, y) => quadrants[usize::from(x.is_gt()) + 2 * usize::from(y.is_gt())] += 1,
+            }
+        });
+        quadrants.iter().product()
+    }
+
+    /// The seconds at which robots form a picture, the first moment their positions are
+    /// bunched together on both axes.
+    ///
+    /// x positions repeat every `width` seconds and y positions every `height`, so the second
+    /// with the least spread in x, and the one with the least in y, can each be found within
+    /// one period and then combined by the Chinese remainder theorem.
+    fn picture(&self) -> usize {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let bunched_x = (0..width)
+            .min_by_key(|&t| spread(self.positions(t).map(|p| p.x)))
+            .unwrap_or(0);
+        let bunched_y = (0..height)
+            .min_by_key(|&t| spread(self.positions(t).map(|p| p.y)))
+            .unwrap_or(0);
+
+        // sieving through the seconds that match x for one that matches y
+        let both = (0..height)
+            .map(|k| bunched_x + k * width)
+            .find(|t| t % height == bunched_y);
+        both.unwrap_or_else(|| {
+            // sizes with a common factor can have no second matching both, so look for the
+            // least spread overall
+            (0..width * height)
+                .min_by_key(|&t| {
+                    spread(self.positions(t).map(|p| p.x)) + spread(self.positions(t).map(|p| p.y))
+                })
+                .unwrap_or(0)
+        })
+    }
+
+    /// The lobby after `seconds`, drawn as the puzzle does with the number of robots on each
+    /// tile, or `.` for none.
+    pub fn frame(&self, seconds: usize) -> String {
+        let mut counts = vec![vec![0usize; self.width as usize]; self.height as usize];
+        self.positions(seconds)
+            .for_each(|p| counts[p.y as usize][p.x as usize] += 1);
+        counts
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|&n| match n {
+                        0 => '.',
+                        1..=9 => char::from_digit(n as u32, 10).unwrap(),
+                        _ => '+',
+                    })
+                    .collect::<String>()
+                    + "\n"
+            })
+            .collect()
+    }
+}
+
+/// The variance of `values` scaled by the square of their count, which keeps it an integer and
+/// orders the same as the variance for a given count.
+fn spread(values: impl Iterator<Item = i32>) -> i64 {
+    let (n, sum, squares) = values.fold((0i64, 0i64, 0i64), |(n, sum, squares), v| {
+        let v = i64::from(v);
+        (n + 1, sum + v, squares + v * v)
+    });
+    n * squares - sum * sum
+}
+
+/// The robots moving a second at a time from 0 until `until`.
+pub struct Motion<'a> {
+    lobby: &'a Lobby,
+    seconds: usize,
+    until: usize,
+}
+
+impl<'a> Motion<'a> {
+    /// Part one's 100 seconds.
+    pub fn part1(lobby: &'a Lobby) -> Self {
+        Motion {
+            lobby,
+            seconds: 0,
+            until: SECONDS,
+        }
+    }
+
+    /// Every second up to the picture part two finds, which is the last frame.
+    pub fn part2(lobby: &'a Lobby) -> Self {
+        Motion {
+            lobby,
+            seconds: 0,
+            until: lobby.picture(),
+        }
+    }
+}
+
+impl Iterator for Motion<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.seconds == self.until {
+            return None;
+        }
+        self.seconds += 1;
+        Some(self.seconds)
+    }
+}
+
+impl fmt::Display for Motion<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.lobby.frame(self.seconds))
+    }
+}
+
+/// The robots in `input` in a lobby `size` tiles wide and high.
+pub fn lobby_generator(input: &str, size: (i32, i32)) -> Result<Lobby, ParseError> {
+    let input = &normalize(input);
+    let re = Regex::new(r"^p=(-?[0-9]+),(-?[0-9]+) v=(-?[0-9]+),(-?[0-9]+)$").unwrap();
+    let robots = input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let caps = re.captures(line).ok_or_else(|| {
+                ParseError::at(i, line, line, "expected a robot like p=0,4 v=3,-3")
+            })?;
+            let number = |n: usize| parse_number(i, line, caps.get(n).unwrap().as_str());
+            Ok(Robot {
+                pos: Point::new(number(1)?, number(2)?),
+                vel: Point::new(number(3)?, number(4)?),
+            })
+        })
+        .collect::<Result<_, ParseError>>()?;
+
+    Lobby::new(robots, size)
+        .ok_or_else(|| ParseError::eof(input, "expected a lobby with a positive width and height"))
+}
+
+#[aoc_generator(day14)]
+pub fn input_generator(input: &str) -> Result<Lobby, ParseError> {
+    lobby_generator(input, LOBBY)
+}
+
+/// `input_generator` for the example, whose robots are in a smaller lobby.
+pub fn example_generator(input: &str) -> Result<Lobby, ParseError> {
+    lobby_generator(input, EXAMPLE_LOBBY)
+}
+
+#[aoc(day14, part1)]
+pub fn part1(lobby: &Lobby) -> usize {
+    lobby.safety_factor(SECONDS)
+}
+
+#[aoc(day14, part2)]
+pub fn part2(lobby: &Lobby) -> usize {
+    lobby.picture()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+
+    fn example() -> Lobby {
+        example_generator(EXAMPLE).unwrap()
+    }
+
+    /// Robots drawing a filled `size` square, scattered by velocities that bring them back
+    /// together at `seconds`.
+    fn picture(lobby: (i32, i32), size: i32, seconds: i32) -> Lobby {
+        let robots = (0..size * size)
+            .map(|i| {
+                let target = Point::new(lobby.0 / 3 + i % size, lobby.1 / 3 + i / size);
+                let vel = Point::new(1 + i % 7, 2 + i % 5);
+                // chosen so that pos + vel * seconds lands on the target
+                let pos = Point::new(
+                    (target.x - vel.x * seconds).rem_euclid(lobby.0),
+                    (target.y - vel.y * seconds).rem_euclid(lobby.1),
+                );
+                Robot { pos, vel }
+            })
+            .collect();
+        Lobby::new(robots, lobby).unwrap()
+    }
+
+    #[test]
+    fn test_input_generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!((input.width, input.height), LOBBY);
+        let input = example_generator(EXAMPLE).unwrap();
+        assert_eq!((input.width, input.height), EXAMPLE_LOBBY);
+        assert_eq!(input.robots.len(), 12);
+        assert_eq!(
+            input.robots[1],
+            Robot {
+                pos: Point::new(6, 3),
+                vel: Point::new(-1, -3),
+            }
+        );
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("p=0,4 v=3,-3\np=6,3 v=-1\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "p=6,3 v=-1", "expected a robot like p=0,4 v=3,-3")
+        );
+
+        let err = input_generator("p=0,4 v=3,-3\np=6,3 v=-1,99999999999\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 12, "99999999999", "expected a number")
+        );
+
+        let err = lobby_generator(EXAMPLE, (0, 7)).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                13,
+                1,
+                "",
+                "expected a lobby with a positive width and height"
+            )
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = example_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 12);
+    }
+
+    #[test]
+    fn test_new() {
+        assert!(Lobby::new(vec![], EXAMPLE_LOBBY).is_some());
+        assert!(Lobby::new(vec![], (0, 7)).is_none());
+        assert!(Lobby::new(vec![], (11, -7)).is_none());
+    }
+
+    #[test]
+    fn test_positions() {
+        // the puzzle's robot at p=2,4 v=2,-3 over its first five seconds
+        let robot = Robot {
+            pos: Point::new(2, 4),
+            vel: Point::new(2, -3),
+        };
+        let lobby = Lobby::new(vec![robot], EXAMPLE_LOBBY).unwrap();
+        let path: Vec<Point> = (0..=5).flat_map(|t| lobby.positions(t)).collect();
+        assert_eq!(
+            path,
+            vec![
+                Point::new(2, 4),
+                Point::new(4, 1),
+                Point::new(6, 5),
+                Point::new(8, 2),
+                Point::new(10, 6),
+                Point::new(1, 3),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_frame() {
+        assert_eq!(
+            example().frame(SECONDS),
+            indoc! {"
+                ......2..1.
+                ...........
+                1..........
+                .11........
+                .....1.....
+                ...12......
+                .1....1....
+            "}
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        let input = example();
+        let result = part1(&input);
+        assert_eq!(result, 12);
+    }
+
+    #[test]
+    fn test_part_two() {
+        // the example has no picture, so robots are placed to draw one
+        let lobby = picture(LOBBY, 10, 6512);
+        assert_eq!(part2(&lobby), 6512);
+        let frame = lobby.frame(6512);
+        assert_eq!(frame.lines().nth(39).unwrap()[33..43], *"1111111111");
+
+        assert_eq!(part2(&picture(EXAMPLE_LOBBY, 3, 40)), 40);
+        // sizes with a common factor
+        assert_eq!(part2(&picture((12, 8), 3, 5)), 5);
+    }
+
+    #[test]
+    fn test_motion() {
+        let input = example();
+        let mut motion = Motion::part1(&input);
+        assert_eq!(motion.by_ref().count(), SECONDS);
+        assert_eq!(motion.to_string(), input.frame(SECONDS));
+
+        let lobby = picture(EXAMPLE_LOBBY, 3, 40);
+        assert_eq!(Motion::part2(&lobby).last(), Some(40));
+    }
+
+    #[test]
+    fn test_empty() {
+        let input = input_generator("").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (0, 0));
+    }
+}
@@ -0,0 +1,402 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+use std::collections::HashSet;
+use std::fmt::{self, Formatter};
+
+/// The larger example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    ##########
+    #..O..O.O#
+    #......O.#
+    #.OO..O.O#
+    #..O@..O.#
+    #O#..O...#
+    #O..O..O.#
+    #.OO.O.OO#
+    #....O...#
+    ##########
+
+    <vv>^<v^>v>^vv^v>v<>v^v<v<^vv<<<^><<><>>v<vvv<>^v^>^<<<><<v<<<v^vv^v>^
+    vvv<<^>^v^^><<>>><>^<<><^vv^^<>vvv<>><^^v>^>vv<>v<<<<v<^v>^<^^>>>^<v<v
+    ><>vv>v^v^<>><>>>><^^>vv>v<^^^>>v^v^<^^>v^^>v^<^v>v<>>v^v^<v>v^^<^^vv<
+    <<v<^>>^^^^>>>v^<>vvv^><v<<<>^^^vv^<vvv>^>v<^^^^v<>^>vvvv><>>v^<<^^^^^
+    ^><^><>>><>^^<<^^v>>><^<v>^<vv>>v>>>^v><>^v><<<<v>>v<v<v>vvv>^<><<>^><
+    ^>><>^v<><^vvv<^^<><v<<<<<><^v<<<><<<^^<v<^^^><^>>^<v^><<<^>>^v<v^v<v^
+    >^>>^v>vv>^<<^v<>><<><<v<<v><>v<^vv<<<>^^v^>^^>>><<^v>>v^v><^^>>^<>vv^
+    <><^^>^^^<><vvvvv<v<v<<>>v<v>v<<^><<><<><<<^^<<<^<<>><<><^^^>^^<>^>v<>
+    ^^>vv<^v^v<vv>^<><v<^v>^^^>>>^^vvv^>vvv<>>>^<^>>>>>^<<^v>^vvv<>^<><<v>
+    v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^
+"};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Tile {
+    Empty,
+    Wall,
+    Box,
+    /// The left and right halves of a box in the wide warehouse.
+    BoxLeft,
+    BoxRight,
+}
+
+/// A warehouse floor with the robot on it.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Floor {
+    tiles: Grid<Tile>,
+    robot: Point,
+}
+
+impl Floor {
+    /// Parses a map, or the same map at double width when `wide`, which only widens what the
+    /// narrow map already accepts.
+    fn parse(map: &str, wide: bool) -> Result<Self, ParseError> {
+        let (map, tiles) = match wide {
+            true => (
+                map.chars()
+                    .map(|c| match c {
+                        '#' => "##",
+                        'O' => "[]",
+                        '@' => "@.",
+                        '\n' => "\n",
+                        _ => "..",
+                    })
+                    .collect(),
+                "#.[]@",
+            ),
+            false => (map.to_string(), "#.O@"),
+        };
+
+        let chars = Grid::parse(&map, |c| tiles.contains(c).then_some(c))?;
+        let robots: Vec<Point> = chars.positions(|&c| c == '@').collect();
+        let [robot] = robots[..] else {
+            let message = format!("expected one robot '@' on the map, found {}", robots.len());
+            return Err(ParseError::eof(&map, &message));
+        };
+        let tiles = chars.map(|&c| match c {
+            '#' => Tile::Wall,
+            'O' => Tile::Box,
+            '[' => Tile::BoxLeft,
+            ']' => Tile::BoxRight,
+            _ => Tile::Empty,
+        });
+        Ok(Floor { tiles, robot })
+    }
+
+    fn at(&self, pos: Point) -> Tile {
+        // everything past the edge is as good as a wall
+        self.tiles.get(pos).copied().unwrap_or(Tile::Wall)
+    }
+
+    /// Moves the robot one tile towards `direction`, pushing every box in the way along with
+    /// it, unless something in the way is up against a wall. Returns whether the robot moved.
+    fn step(&mut self, direction: Direction) -> bool {
+        let vertical = direction.offset().x == 0;
+        let mut pushed = vec![];
+        let mut seen = HashSet::new();
+        let mut front = vec![self.robot];
+
+        // a box pushed up or down pushes whatever is ahead of both its halves, so the boxes in
+        // the way form a tree rather than a line
+        while let Some(pos) = front.pop() {
+            let ahead = pos + direction;
+            let halves = match self.at(ahead) {
+                Tile::Wall => return false,
+                Tile::Empty => continue,
+                Tile::Box => vec![ahead],
+                Tile::BoxLeft if vertical => vec![ahead, ahead + Direction::Right],
+                Tile::BoxRight if vertical => vec![ahead, ahead + Direction::Left],
+                Tile::BoxLeft | Tile::BoxRight => vec![ahead],
+            };
+            halves
+                .into_iter()
+                .filter(|&p| seen.insert(p))
+                .for_each(|p| {
+                    pushed.push(p);
+                    front.push(p);
+                });
+        }
+
+        let tiles: Vec<Tile> = pushed.iter().map(|&p| self.at(p)).collect();
+        pushed
+            .iter()
+            .for_each(|&p| *self.tiles.get_mut(p).unwrap() = Tile::Empty);
+        pushed.iter().zip(tiles).for_each(|(&p, tile)| {
+            *self.tiles.get_mut(p + direction).unwrap() = tile;
+        });
+        self.robot += direction.offset();
+        true
+    }
+
+    /// The sum of each box's GPS coordinate, 100 times its distance from the top edge plus its
+    /// distance from the left edge.
+    fn gps_sum(&self) -> i64 {
+        self.tiles
+            .positions(|&t| matches!(t, Tile::Box | Tile::BoxLeft))
+            .map(|p| 100 * i64::from(p.y) + i64::from(p.x))
+            .sum()
+    }
+}
+
+impl fmt::Display for Floor {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (rows, cols) = self.tiles.dimensions();
+        for row in 0..rows {
+            for col in 0..cols {
+                let pos = Point::from_index(row, col);
+                let c = match self.at(pos) {
+                    _ if pos == self.robot => '@',
+                    Tile::Empty => '.',
+                    Tile::Wall => '#',
+                    Tile::Box => 'O',
+                    Tile::BoxLeft => '[',
+                    Tile::BoxRight => ']',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Warehouse {
+    floor: Floor,
+    wide: Floor,
+    moves: Vec<Direction>,
+}
+
+impl Warehouse {
+    /// `floor` after the robot has made every move.
+    fn run(&self, floor: &Floor) -> Floor {
+        let mut floor = floor.clone();
+        self.moves.iter().for_each(|&d| {
+            floor.step(d);
+        });
+        floor
+    }
+}
+
+#[aoc_generator(day15)]
+pub fn input_generator(input: &str) -> Result<Warehouse, ParseError> {
+    let input = &normalize(input);
+    let (map, moves_s) = input.split_once("\n\n").ok_or_else(|| {
+        ParseError::eof(input, "expected a blank line between the map and the moves")
+    })?;
+
+    let floor = Floor::parse(map, false)?;
+    let wide = Floor::parse(map, true)?;
+
+    // moves start after the map and the blank separator line
+    let offset = map.lines().count() + 1;
+    let moves = moves_s
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.char_indices().map(move |(j, c)| match c {
+                '^' => Ok(Direction::Up),
+                '>' => Ok(Direction::Right),
+                'v' => Ok(Direction::Down),
+                '<' => Ok(Direction::Left),
+                _ => Err(ParseError::at(
+                    offset + i,
+                    line,
+                    &line[j..j + c.len_utf8()],
+                    "expected a move, one of ^, >, v or <",
+                )),
+            })
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(Warehouse { floor, wide, moves })
+}
+
+#[aoc(day15, part1)]
+pub fn part1(warehouse: &Warehouse) -> i64 {
+    warehouse.run(&warehouse.floor).gps_sum()
+}
+
+#[aoc(day15, part2)]
+pub fn part2(warehouse: &Warehouse) -> i64 {
+    warehouse.run(&warehouse.wide).gps_sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+
+    const SMALL: &str = indoc! {"
+        ########
+        #..O.O.#
+        ##@.O..#
+        #...O..#
+        #.#.O..#
+        #...O..#
+        #......#
+        ########
+
+        <^^>>>vv<v>>v<<
+    "};
+
+    const WIDE: &str = indoc! {"
+        #######
+        #...#.#
+        #.....#
+        #..OO@#
+        #..O..#
+        #.....#
+        #######
+
+        <vv<<^^<<^^
+    "};
+
+    #[test]
+    fn test_input_generator() {
+        let input = input_generator(SMALL).unwrap();
+        assert_eq!(input.floor.tiles.dimensions(), (8, 8));
+        assert_eq!(input.floor.robot, Point::new(2, 2));
+        assert_eq!(input.wide.tiles.dimensions(), (8, 16));
+        assert_eq!(input.wide.robot, Point::new(4, 2));
+        assert_eq!(input.moves.len(), 15);
+        assert_eq!(input.moves[..2], [Direction::Left, Direction::Up]);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.moves.len(), 700);
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("#.@#\n\n<>x^\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(3, 3, "x", "expected a move, one of ^, >, v or <")
+        );
+
+        let err = input_generator("#..#\n\n<>\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "", "expected one robot '@' on the map, found 0")
+        );
+
+        let err = input_generator("#.@#\n#.a#\n\n<>\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "a", "unexpected character"));
+
+        let err = input_generator("#.@#\n<>\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(
+                3,
+                1,
+                "",
+                "expected a blank line between the map and the moves"
+            )
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(SMALL)).unwrap();
+        assert_eq!(part1(&input), 2028);
+    }
+
+    #[test]
+    fn test_run() {
+        let input = input_generator(SMALL).unwrap();
+        assert_eq!(
+            input.run(&input.floor).to_string(),
+            indoc! {"
+                ########
+                #....OO#
+                ##.....#
+                #.....O#
+                #.#O@..#
+                #...O..#
+                #...O..#
+                ########
+            "}
+        );
+
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(
+            input.run(&input.floor).to_string(),
+            indoc! {"
+                ##########
+                #.O.O.OOO#
+                #........#
+                #OO......#
+                #OO@.....#
+                #O#.....O#
+                #O.....OO#
+                #O.....OO#
+                #OO....OO#
+                ##########
+            "}
+        );
+
+        let input = input_generator(WIDE).unwrap();
+        assert_eq!(
+            input.run(&input.wide).to_string(),
+            indoc! {"
+                ##############
+                ##...[].##..##
+                ##...@.[]...##
+                ##....[]....##
+                ##..........##
+                ##..........##
+                ##############
+            "}
+        );
+    }
+
+    #[test]
+    fn test_push_tree() {
+        // the lower box is pushed half a box left, so that pushing it up pushes both boxes above,
+        // until they meet the wall
+        let input = input_generator(indoc! {"
+            #######
+            #.....#
+            #.....#
+            #.OO..#
+            #..O@.#
+            #.....#
+            #######
+
+            <v<<^^^^
+        "})
+        .unwrap();
+        assert_eq!(
+            input.run(&input.wide).to_string(),
+            indoc! {"
+                ##############
+                ##..[][]....##
+                ##...[].....##
+                ##...@......##
+                ##..........##
+                ##..........##
+                ##############
+            "}
+        );
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part1(&input_generator(SMALL).unwrap()), 2028);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part1(&input);
+        assert_eq!(result, 10092);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part2(&input_generator(WIDE).unwrap()), 618);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part2(&input);
+        assert_eq!(result, 9021);
+    }
+}
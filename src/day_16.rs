@@ -0,0 +1,387 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use crate::grid::Grid;
+use crate::input::normalize;
+use aoc_runner_derive::{aoc, aoc_generator};
+use indoc::indoc;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+
+/// The first example input from the puzzle description.
+pub const EXAMPLE: &str = indoc! {"
+    ###############
+    #.......#....E#
+    #.#.###.#.###.#
+    #.....#.#...#.#
+    #.###.#####.#.#
+    #.#.#.......#.#
+    #.#.#####.###.#
+    #...........#.#
+    ###.#.#####.#.#
+    #...#.....#.#.#
+    #.#.#.###.#.#.#
+    #.....#...#.#.#
+    #.###.#.#.#.#.#
+    #S..#.....#...#
+    ###############
+"};
+
+/// The score of a step forward and of a quarter turn either way.
+const STEP: u64 = 1;
+const TURN: u64 = 1000;
+
+/// Where the reindeer is and which way it faces.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy)]
+struct Reindeer {
+    pos: Point,
+    facing: Direction,
+}
+
+impl Reindeer {
+    /// Every state one move from this one, with the score of the move.
+    fn moves(self) -> [(Reindeer, u64); 3] {
+        let Reindeer { pos, facing } = self;
+        [
+            (
+                Reindeer {
+                    pos: pos + facing,
+                    facing,
+                },
+                STEP,
+            ),
+            (
+                Reindeer {
+                    pos,
+                    facing: facing.rotate_cw(),
+                },
+                TURN,
+            ),
+            (
+                Reindeer {
+                    pos,
+                    facing: facing.rotate_ccw(),
+                },
+                TURN,
+            ),
+        ]
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Maze {
+    walls: Grid<bool>,
+    start: Point,
+    end: Point,
+}
+
+/// Every path from the start to the end with the lowest score.
+#[derive(Debug)]
+struct BestPaths {
+    score: u64,
+    /// The end states reached with `score`, one per facing that ties.
+    ends: Vec<Reindeer>,
+    /// For each state, the states before it on its lowest scoring paths from the start.
+    came_from: HashMap<Reindeer, Vec<Reindeer>>,
+}
+
+impl BestPaths {
+    /// The tiles on any of the paths, found by walking back from the ends.
+    fn tiles(&self) -> HashSet<Point> {
+        let mut seen: HashSet<Reindeer> = self.ends.iter().copied().collect();
+        let mut stack = self.ends.clone();
+        while let Some(state) = stack.pop() {
+            self.came_from
+                .get(&state)
+                .into_iter()
+                .flatten()
+                .for_each(|&prev| {
+                    if seen.insert(prev) {
+                        stack.push(prev);
+                    }
+                });
+        }
+        seen.iter().map(|state| state.pos).collect()
+    }
+}
+
+impl Maze {
+    fn open(&self, pos: Point) -> bool {
+        self.walls.get(pos) == Some(&false)
+    }
+
+    /// Dijkstra's algorithm from the start facing east, keeping every predecessor that ties
+    /// for a state's lowest score. `None` if the end can't be reached.
+    fn best_paths(&self) -> Option<BestPaths> {
+        let start = Reindeer {
+            pos: self.start,
+            facing: Direction::Right,
+        };
+        let mut scores = HashMap::from([(start, 0)]);
+        let mut came_from: HashMap<Reindeer, Vec<Reindeer>> = HashMap::new();
+        let mut queue = BinaryHeap::from([Reverse((0, start))]);
+        let mut best = None;
+        let mut ends = vec![];
+
+        while let Some(Reverse((score, state))) = queue.pop() {
+            if score > scores[&state] {
+                continue;
+            }
+            // states come off the queue in score order, so nothing after this ties the best
+            if best.is_some_and(|best| score > best) {
+                break;
+            }
+            if state.pos == self.end {
+                best = Some(score);
+                ends.push(state);
+                continue;
+            }
+
+            for (next, cost) in state.moves() {
+                if !self.open(next.pos) {
+                    continue;
+                }
+                let next_score = score + cost;
+                match scores.get(&next) {
+                    Some(&s) if s < next_score => {}
+                    Some(&s) if s == next_score => came_from.entry(next).or_default().push(state),
+                    _ => {
+                        scores.insert(next, next_score);
+                        came_from.insert(next, vec![state]);
+                        queue.push(Reverse((next_score, next)));
+                    }
+                }
+            }
+        }
+
+        best.map(|score| BestPaths {
+            score,
+            ends,
+            came_from,
+        })
+    }
+}
+
+#[aoc_generator(day16)]
+pub fn input_generator(input: &str) -> Result<Maze, ParseError> {
+    let input = &normalize(input);
+    let chars = Grid::parse(input, |c| "#.SE".contains(c).then_some(c))?;
+    let find_one = |tile: char, name: &str| {
+        let found: Vec<Point> = chars.positions(|&c| c == tile).collect();
+        match found[..] {
+            [pos] => Ok(pos),
+            _ => Err(ParseError::eof(
+                input,
+                &format!(
+                    "expected one {name} '{tile}' in the maze, found {}",
+                    found.len()
+                ),
+            )),
+        }
+    };
+    let start = find_one('S', "start")?;
+    let end = find_one('E', "end")?;
+
+    Ok(Maze {
+        walls: chars.map(|&c| c == '#'),
+        start,
+        end,
+    })
+}
+
+/// The lowest score a reindeer could get, or 0 if it can't reach the end.
+#[aoc(day16, part1)]
+pub fn part1(maze: &Maze) -> u64 {
+    maze.best_paths().map_or(0, |best| best.score)
+}
+
+/// The number of tiles on at least one of the best paths, or 0 if there are none.
+#[aoc(day16, part2)]
+pub fn part2(maze: &Maze) -> usize {
+    maze.best_paths().map_or(0, |best| best.tiles().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::windows_style;
+    use proptest::prelude::*;
+
+    const SECOND: &str = indoc! {"
+        #################
+        #...#...#...#..E#
+        #.#.#.#.#.#.#.#.#
+        #.#.#.#...#...#.#
+        #.#.#.#.###.#.#.#
+        #...#.#.#.....#.#
+        #.#.#.#.#.#####.#
+        #.#...#.#.#.....#
+        #.#.#####.#.###.#
+        #.#.#.......#...#
+        #.#.###.#####.###
+        #.#.#...#.....#.#
+        #.#.#.#####.###.#
+        #.#.#.........#.#
+        #.#.#.#########.#
+        #S#.............#
+        #################
+    "};
+
+    /// Every state's lowest score from the start, by relaxing every move until nothing changes.
+    fn scores_naive(maze: &Maze) -> HashMap<Reindeer, u64> {
+        let start = Reindeer {
+            pos: maze.start,
+            facing: Direction::Right,
+        };
+        let mut scores = HashMap::from([(start, 0)]);
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (state, score) in scores.clone() {
+                for (next, cost) in state.moves() {
+                    let best = scores.get(&next).copied().unwrap_or(u64::MAX);
+                    if maze.open(next.pos) && score + cost < best {
+                        scores.insert(next, score + cost);
+                        changed = true;
+                    }
+                }
+            }
+        }
+        scores
+    }
+
+    /// The lowest score and the tiles on a best path, where a state is on one when its score
+    /// from the start plus its score to the end is the lowest.
+    fn best_naive(maze: &Maze) -> Option<(u64, HashSet<Point>)> {
+        let from_start = scores_naive(maze);
+        let at_end = |state: &Reindeer| state.pos == maze.end;
+        let score = from_start
+            .iter()
+            .filter(|(state, _)| at_end(state))
+            .map(|(_, &s)| s)
+            .min()?;
+
+        // scores to the end, relaxing the same moves backwards
+        let mut to_end: HashMap<Reindeer, u64> = from_start
+            .keys()
+            .filter(|state| at_end(state))
+            .map(|&state| (state, 0))
+            .collect();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for &state in from_start.keys() {
+                for (next, cost) in state.moves() {
+                    let Some(&after) = to_end.get(&next) else {
+                        continue;
+                    };
+                    if to_end.get(&state).is_none_or(|&s| cost + after < s) {
+                        to_end.insert(state, cost + after);
+                        changed = true;
+                    }
+                }
+            }
+        }
+
+        let tiles = from_start
+            .iter()
+            .filter(|&(state, s)| to_end.get(state).is_some_and(|e| s + e == score))
+            .map(|(state, _)| state.pos)
+            .collect();
+        Some((score, tiles))
+    }
+
+    /// Small mazes of open tiles and walls, with the start and end on different tiles.
+    fn mazes() -> impl Strategy<Value = Maze> {
+        (1usize..7, 2usize..7)
+            .prop_flat_map(|(rows, cols)| {
+                let tiles = prop::collection::vec(prop::bool::weighted(0.3), rows * cols);
+                (Just(cols), tiles, 0..rows * cols, 0..rows * cols)
+            })
+            .prop_filter("start on the end", |(_, _, start, end)| start != end)
+            .prop_map(|(cols, walls, start, end)| {
+                let tiles: Vec<char> = (0..walls.len())
+                    .map(|i| match i {
+                        _ if i == start => 'S',
+                        _ if i == end => 'E',
+                        _ if walls[i] => '#',
+                        _ => '.',
+                    })
+                    .collect();
+                let rows: Vec<String> = tiles.chunks(cols).map(|r| r.iter().collect()).collect();
+                input_generator(&rows.join("\n")).unwrap()
+            })
+    }
+
+    #[test]
+    fn test_input_generator() {
+        let input = input_generator(EXAMPLE).unwrap();
+        assert_eq!(input.walls.dimensions(), (15, 15));
+        assert_eq!(input.start, Point::new(1, 13));
+        assert_eq!(input.end, Point::new(13, 1));
+        assert_eq!(input.walls.get(Point::new(8, 1)), Some(&true));
+    }
+
+    #[test]
+    fn test_input_generator_malformed() {
+        let err = input_generator("#S.#\n#.x#\n#.E#\n").unwrap_err();
+        assert_eq!(err, ParseError::new(2, 3, "x", "unexpected character"));
+
+        let err = input_generator("#S.#\n#..#\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(3, 1, "", "expected one end 'E' in the maze, found 0")
+        );
+
+        let err = input_generator("#S.#\n#.S#\n#.E#\n").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(4, 1, "", "expected one start 'S' in the maze, found 2")
+        );
+    }
+
+    #[test]
+    fn test_input_generator_crlf() {
+        let input = input_generator(&windows_style(EXAMPLE)).unwrap();
+        assert_eq!(part1(&input), 7036);
+    }
+
+    #[test]
+    fn test_turns() {
+        // straight ahead, one turn, and two turns to face back the way the reindeer came
+        assert_eq!(part1(&input_generator("S..E\n").unwrap()), 3);
+        assert_eq!(part1(&input_generator(".E\nS.\n").unwrap()), 1002);
+        assert_eq!(part1(&input_generator("E.S\n").unwrap()), 2002);
+    }
+
+    #[test]
+    fn test_unreachable() {
+        let input = input_generator("S#E\n").unwrap();
+        assert_eq!((part1(&input), part2(&input)), (0, 0));
+    }
+
+    #[test]
+    fn test_part_one() {
+        assert_eq!(part1(&input_generator(SECOND).unwrap()), 11048);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part1(&input);
+        assert_eq!(result, 7036);
+    }
+
+    #[test]
+    fn test_part_two() {
+        assert_eq!(part2(&input_generator(SECOND).unwrap()), 64);
+
+        let input = input_generator(EXAMPLE).unwrap();
+        let result = part2(&input);
+        assert_eq!(result, 45);
+    }
+
+    proptest! {
+        #[test]
+        fn test_best_paths_match_naive(maze in mazes()) {
+            let best = maze.best_paths().map(|best| (best.score, best.tiles()));
+            prop_assert_eq!(best, best_naive(&maze));
+        }
+    }
+}
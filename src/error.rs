@@ -0,0 +1,128 @@
+use std::error::Error;
+use std::fmt;
+use std::fmt::Formatter;
+use std::io;
+use std::str::FromStr;
+
+/// A problem found while parsing puzzle input, located by 1-based line and column.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, message: &str) -> Self {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            message: message.to_string(),
+        }
+    }
+
+    /// An error for `token`, which must be a slice of `line`, the zero-based `line_idx`-th line
+    /// of the input.
+    pub fn at(line_idx: usize, line: &str, token: &str, message: &str) -> Self {
+        let offset = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|offset| *offset <= line.len())
+            .unwrap_or(0);
+        let column = line[..offset].chars().count() + 1;
+
+        ParseError::new(line_idx + 1, column, token, message)
+    }
+
+    /// An error for something missing from the input as a whole, reported just past its end.
+    pub fn eof(input: &str, message: &str) -> Self {
+        ParseError::new(input.lines().count() + 1, 1, "", message)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line, self.column, self.message
+        )?;
+        if !self.text.is_empty() {
+            write!(f, " (found {:?})", self.text)?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for ParseError {}
+
+/// A problem with puzzle input read from a stream: either reading failed or what was read
+/// doesn't parse.
+#[derive(Debug)]
+pub enum ReadError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl From<io::Error> for ReadError {
+    fn from(e: io::Error) -> Self {
+        ReadError::Io(e)
+    }
+}
+
+impl From<ParseError> for ReadError {
+    fn from(e: ParseError) -> Self {
+        ReadError::Parse(e)
+    }
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Io(e) => write!(f, "{e}"),
+            ReadError::Parse(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl Error for ReadError {}
+
+/// Parses `token`, a slice of the zero-based `line_idx`-th `line`, as a number.
+pub fn parse_number<T: FromStr>(line_idx: usize, line: &str, token: &str) -> Result<T, ParseError> {
+    token
+        .parse()
+        .map_err(|_| ParseError::at(line_idx, line, token, "expected a number"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_at_column() {
+        let line = "12 ab 34";
+        let err = ParseError::at(4, line, &line[3..5], "expected a number");
+        assert_eq!(err, ParseError::new(5, 4, "ab", "expected a number"));
+        assert_eq!(
+            err.to_string(),
+            "line 5, column 4: expected a number (found \"ab\")"
+        );
+    }
+
+    #[test]
+    fn test_eof() {
+        let err = ParseError::eof("a\nb\n", "missing section");
+        assert_eq!(err.to_string(), "line 3, column 1: missing section");
+    }
+
+    #[test]
+    fn test_parse_number() {
+        let line = "7: 1x";
+        assert_eq!(parse_number::<u32>(0, line, &line[..1]), Ok(7));
+        assert_eq!(
+            parse_number::<u32>(0, line, &line[3..]),
+            Err(ParseError::new(1, 4, "1x", "expected a number"))
+        );
+    }
+}
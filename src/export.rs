@@ -0,0 +1,344 @@
+#[cfg(feature = "day05")]
+use crate::day_05;
+#[cfg(feature = "day10")]
+use crate::day_10;
+use crate::error::ParseError;
+use crate::geom::Point;
+use std::f64::consts::PI;
+use std::fmt::Write;
+
+/// Pixels per unit of node position in SVG output.
+const SPACING: f64 = 48.0;
+const RADIUS: f64 = 14.0;
+const MARGIN: f64 = 24.0;
+const COLOR: &str = "#555555";
+const HIGHLIGHT: &str = "#d62728";
+
+#[derive(Debug, Clone, PartialEq)]
+struct Node {
+    id: String,
+    label: String,
+    /// Where the node is drawn, in units of node spacing.
+    pos: Option<Point>,
+    highlight: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Edge {
+    from: usize,
+    to: usize,
+    highlight: bool,
+}
+
+/// A directed graph that can be written as Graphviz DOT or drawn as SVG, with some of its nodes
+/// and edges highlighted.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Graph {
+    name: String,
+    nodes: Vec<Node>,
+    edges: Vec<Edge>,
+}
+
+impl Graph {
+    pub fn new(name: &str) -> Self {
+        Graph {
+            name: name.to_string(),
+            nodes: vec![],
+            edges: vec![],
+        }
+    }
+
+    /// Adds a node. Node ids must be unique.
+    pub fn add_node(&mut self, id: &str, label: &str, highlight: bool) {
+        self.nodes.push(Node {
+            id: id.to_string(),
+            label: label.to_string(),
+            pos: None,
+            highlight,
+        });
+    }
+
+    /// Adds a node drawn at `pos`. Unless every node has a position, the nodes are drawn evenly
+    /// spaced around a circle in the order they were added instead.
+    pub fn add_node_at(&mut self, id: &str, label: &str, pos: Point, highlight: bool) {
+        self.add_node(id, label, highlight);
+        self.nodes.last_mut().unwrap().pos = Some(pos);
+    }
+
+    /// Adds an edge between the nodes with ids `from` and `to`, ignoring it if either is missing.
+    pub fn add_edge(&mut self, from: &str, to: &str, highlight: bool) {
+        let index = |id: &str| self.nodes.iter().position(|n| n.id == id);
+        if let (Some(from), Some(to)) = (index(from), index(to)) {
+            self.edges.push(Edge {
+                from,
+                to,
+                highlight,
+            });
+        }
+    }
+
+    /// The graph in Graphviz DOT, with node positions pinned for `neato -n` when it has them.
+    pub fn to_dot(&self) -> String {
+        let mut dot = format!("digraph {:?} {{\n", self.name);
+        dot += "    node [shape=circle];\n";
+        let positioned = self.positions().is_some();
+        self.nodes.iter().for_each(|node| {
+            let mut attrs = format!("label={:?}", node.label);
+            if let Some(pos) = node.pos.filter(|_| positioned) {
+                let (x, y) = (f64::from(pos.x) * SPACING, f64::from(-pos.y) * SPACING);
+                write!(attrs, ", pos=\"{x},{y}!\"").unwrap();
+            }
+            if node.highlight {
+                write!(attrs, ", color=\"{HIGHLIGHT}\", penwidth=2").unwrap();
+            }
+            writeln!(dot, "    {:?} [{attrs}];", node.id).unwrap();
+        });
+        self.edges.iter().for_each(|edge| {
+            let (from, to) = (&self.nodes[edge.from].id, &self.nodes[edge.to].id);
+            let attrs = match edge.highlight {
+                true => format!(" [color=\"{HIGHLIGHT}\", penwidth=2]"),
+                false => String::new(),
+            };
+            writeln!(dot, "    {from:?} -> {to:?}{attrs};").unwrap();
+        });
+        dot + "}\n"
+    }
+
+    /// The position of every node, if they all have one.
+    fn positions(&self) -> Option<Vec<Point>> {
+        self.nodes.iter().map(|n| n.pos).collect()
+    }
+
+    /// The centre of each node in pixels.
+    fn layout(&self) -> Vec<(f64, f64)> {
+        match self.positions() {
+            Some(positions) => positions
+                .iter()
+                .map(|p| (f64::from(p.x) * SPACING, f64::from(p.y) * SPACING))
+                .collect(),
+            None => {
+                // space the nodes a little over a node spacing apart around the circle
+                let n = self.nodes.len() as f64;
+                let r = (n * SPACING * 1.5 / (2.0 * PI)).max(SPACING);
+                (0..self.nodes.len())
+                    .map(|i| {
+                        let angle = 2.0 * PI * i as f64 / n - PI / 2.0;
+                        (r + r * angle.cos(), r + r * angle.sin())
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// The graph drawn as an SVG image, with highlighted nodes and edges in red.
+    pub fn to_svg(&self) -> String {
+        let layout: Vec<(f64, f64)> = self
+            .layout()
+            .iter()
+            .map(|(x, y)| (x + MARGIN + RADIUS, y + MARGIN + RADIUS))
+            .collect();
+        let width = layout.iter().map(|p| p.0).fold(0.0, f64::max) + MARGIN + RADIUS;
+        let height = layout.iter().map(|p| p.1).fold(0.0, f64::max) + MARGIN + RADIUS;
+
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width:.0}\" height=\"{height:.0}\" \
+             font-family=\"sans-serif\" font-size=\"12\">\n"
+        );
+        svg += "  <defs>\n";
+        for (id, color) in [("arrow", COLOR), ("arrow-highlight", HIGHLIGHT)] {
+            writeln!(
+                svg,
+                "    <marker id=\"{id}\" viewBox=\"0 0 10 10\" refX=\"10\" refY=\"5\" \
+                 markerWidth=\"6\" markerHeight=\"6\" orient=\"auto\">\
+                 <path d=\"M0,0 L10,5 L0,10 z\" fill=\"{color}\"/></marker>"
+            )
+            .unwrap();
+        }
+        svg += "  </defs>\n";
+
+        self.edges.iter().for_each(|edge| {
+            let ((x1, y1), (x2, y2)) = (layout[edge.from], layout[edge.to]);
+            let len = (x2 - x1).hypot(y2 - y1).max(f64::EPSILON);
+            // run from the edge of one circle to the edge of the other
+            let (dx, dy) = ((x2 - x1) / len * RADIUS, (y2 - y1) / len * RADIUS);
+            let (color, marker, width) = match edge.highlight {
+                true => (HIGHLIGHT, "arrow-highlight", 2),
+                false => (COLOR, "arrow", 1),
+            };
+            writeln!(
+                svg,
+                "  <line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{color}\" \
+                 stroke-width=\"{width}\" marker-end=\"url(#{marker})\"/>",
+                x1 + dx,
+                y1 + dy,
+                x2 - dx,
+                y2 - dy
+            )
+            .unwrap();
+        });
+
+        self.nodes.iter().zip(&layout).for_each(|(node, (x, y))| {
+            let (stroke, width) = match node.highlight {
+                true => (HIGHLIGHT, 2),
+                false => (COLOR, 1),
+            };
+            writeln!(
+                svg,
+                "  <circle cx=\"{x:.1}\" cy=\"{y:.1}\" r=\"{RADIUS}\" fill=\"white\" \
+                 stroke=\"{stroke}\" stroke-width=\"{width}\"/>"
+            )
+            .unwrap();
+            writeln!(
+                svg,
+                "  <text x=\"{x:.1}\" y=\"{y:.1}\" text-anchor=\"middle\" \
+                 dominant-baseline=\"central\">{}</text>",
+                escape(&node.label)
+            )
+            .unwrap();
+        });
+
+        svg + "</svg>\n"
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// The days with a graph to export, and what their highlight index picks out.
+pub const DAYS: &[(u8, &str)] = &[
+    #[cfg(feature = "day05")]
+    (5, "the rules an update breaks, by index of the update"),
+    #[cfg(feature = "day10")]
+    (
+        10,
+        "the trails from a trailhead, by index of the trailhead in reading order",
+    ),
+];
+
+/// Parses `input` for `day` and builds its graph with the `highlight`th item highlighted, or
+/// `None` if the day has no graph. An index past the end highlights nothing.
+#[cfg_attr(
+    not(any(feature = "day05", feature = "day10")),
+    allow(unused_variables)
+)]
+pub fn export(day: u8, input: &str, highlight: Option<usize>) -> Option<Result<Graph, ParseError>> {
+    match day {
+        #[cfg(feature = "day05")]
+        5 => Some(day_05::input_generator(input).map(|(rules, updates)| {
+            day_05::rule_graph(&rules, highlight.and_then(|i| updates.get(i)))
+        })),
+        #[cfg(feature = "day10")]
+        10 => Some(day_10::input_generator(input).map(|map| day_10::trail_graph(&map, highlight))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(any(feature = "day05", feature = "day10"))]
+    use std::{env, fs};
+
+    /// Compares `actual` to the snapshot file `name`, or rewrites the file when `UPDATE_SNAPSHOTS`
+    /// is set.
+    #[cfg(any(feature = "day05", feature = "day10"))]
+    fn assert_snapshot(name: &str, actual: &str) {
+        let path = format!("{}/tests/snapshots/{name}", env!("CARGO_MANIFEST_DIR"));
+        if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+            fs::write(&path, actual).unwrap();
+            return;
+        }
+        let expected = fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            expected == actual,
+            "{name} doesn't match its snapshot, rerun with UPDATE_SNAPSHOTS=1 to accept:\n{actual}"
+        );
+    }
+
+    fn graph() -> Graph {
+        let mut graph = Graph::new("g");
+        graph.add_node("a", "A", false);
+        graph.add_node_at("b", "<b>", Point::new(1, 1), true);
+        graph.add_node("c", "C", false);
+        graph.add_edge("a", "b", true);
+        graph.add_edge("b", "c", false);
+        graph.add_edge("b", "missing", false);
+        graph
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let exp = "digraph \"g\" {
+    node [shape=circle];
+    \"a\" [label=\"A\"];
+    \"b\" [label=\"<b>\", color=\"#d62728\", penwidth=2];
+    \"c\" [label=\"C\"];
+    \"a\" -> \"b\" [color=\"#d62728\", penwidth=2];
+    \"b\" -> \"c\";
+}
+";
+        assert_eq!(graph().to_dot(), exp);
+    }
+
+    #[test]
+    fn test_to_svg() {
+        let svg = graph().to_svg();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<circle").count(), 3);
+        assert_eq!(svg.matches("<line").count(), 2);
+        assert_eq!(svg.matches("url(#arrow-highlight)").count(), 1);
+        assert!(svg.contains(">&lt;b&gt;</text>"));
+    }
+
+    #[test]
+    fn test_positions() {
+        let mut graph = Graph::new("g");
+        graph.add_node_at("a", "A", Point::new(0, 0), false);
+        graph.add_node_at("b", "B", Point::new(2, 1), false);
+        assert_eq!(graph.layout(), [(0.0, 0.0), (96.0, 48.0)]);
+        assert!(graph
+            .to_dot()
+            .contains("\"b\" [label=\"B\", pos=\"96,-48!\"];"));
+        assert!(graph.to_svg().contains("width=\"172\" height=\"124\""));
+    }
+
+    #[test]
+    #[cfg(feature = "day05")]
+    fn test_rule_graph_snapshot() {
+        let example = day_05::EXAMPLE;
+        assert_snapshot(
+            "day_05.dot",
+            &export(5, example, None).unwrap().unwrap().to_dot(),
+        );
+        // 75,97,47,61,53 breaks 97|75
+        let graph = export(5, example, Some(3)).unwrap().unwrap();
+        assert_snapshot("day_05_update_3.dot", &graph.to_dot());
+        assert_snapshot("day_05_update_3.svg", &graph.to_svg());
+    }
+
+    #[test]
+    #[cfg(feature = "day10")]
+    fn test_trail_graph_snapshot() {
+        let example = day_10::EXAMPLE;
+        assert_snapshot(
+            "day_10.dot",
+            &export(10, example, None).unwrap().unwrap().to_dot(),
+        );
+        let graph = export(10, example, Some(0)).unwrap().unwrap();
+        assert_snapshot("day_10_trailhead_0.dot", &graph.to_dot());
+        assert_snapshot("day_10_trailhead_0.svg", &graph.to_svg());
+    }
+
+    #[test]
+    #[cfg(feature = "day05")]
+    fn test_export() {
+        assert!(export(4, "", None).is_none());
+        assert!(export(5, "47|53", None).unwrap().is_err());
+        let graph = export(5, day_05::EXAMPLE, Some(99)).unwrap().unwrap();
+        assert_eq!(graph, export(5, day_05::EXAMPLE, None).unwrap().unwrap());
+    }
+}
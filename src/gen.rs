@@ -0,0 +1,532 @@
+use rand::prelude::*;
+use rand::rngs::StdRng;
+
+/// A seeded generator of valid puzzle input for one day, scaled by a size parameter.
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub day: u8,
+    /// What `size` counts, for help text.
+    pub size_unit: &'static str,
+    /// Roughly the size of the real input.
+    pub default_size: usize,
+    generate: fn(&mut StdRng, usize) -> String,
+}
+
+impl Generator {
+    /// The input for `seed` and `size`; the same arguments always give the same input.
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut StdRng::seed_from_u64(seed), size)
+    }
+}
+
+/// Every day with a generator, ordered by day.
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: 1,
+        size_unit: "pairs of location ids",
+        default_size: 1000,
+        generate: location_lists,
+    },
+    Generator {
+        day: 2,
+        size_unit: "reports",
+        default_size: 1000,
+        generate: reports,
+    },
+    Generator {
+        day: 3,
+        size_unit: "instructions",
+        default_size: 800,
+        generate: corrupted_memory,
+    },
+    Generator {
+        day: 4,
+        size_unit: "rows and columns",
+        default_size: 140,
+        generate: word_search,
+    },
+    Generator {
+        day: 5,
+        size_unit: "updates",
+        default_size: 200,
+        generate: print_queue,
+    },
+    Generator {
+        day: 6,
+        size_unit: "rows and columns",
+        default_size: 130,
+        generate: guard_map,
+    },
+    Generator {
+        day: 7,
+        size_unit: "calibrations",
+        default_size: 850,
+        generate: calibrations,
+    },
+    Generator {
+        day: 8,
+        size_unit: "rows and columns",
+        default_size: 50,
+        generate: antenna_map,
+    },
+    Generator {
+        day: 9,
+        size_unit: "files",
+        default_size: 10000,
+        generate: disk_map,
+    },
+    Generator {
+        day: 10,
+        size_unit: "rows and columns",
+        default_size: 50,
+        generate: topographic_map,
+    },
+    Generator {
+        day: 11,
+        size_unit: "stones",
+        default_size: 8,
+        generate: stones,
+    },
+    Generator {
+        day: 12,
+        size_unit: "rows and columns",
+        default_size: 140,
+        generate: garden_plots,
+    },
+    Generator {
+        day: 13,
+        size_unit: "claw machines",
+        default_size: 320,
+        generate: claw_machines,
+    },
+    Generator {
+        day: 14,
+        size_unit: "robots",
+        default_size: 500,
+        generate: robots,
+    },
+    Generator {
+        day: 15,
+        size_unit: "rows and columns",
+        default_size: 50,
+        generate: warehouse,
+    },
+    Generator {
+        day: 16,
+        size_unit: "rows and columns",
+        default_size: 141,
+        generate: reindeer_maze,
+    },
+];
+
+/// The generator for `day`, if there is one.
+pub fn find(day: u8) -> Option<Generator> {
+    GENERATORS.iter().find(|g| g.day == day).copied()
+}
+
+fn join_lines(lines: impl Iterator<Item = String>) -> String {
+    lines.collect::<Vec<_>>().join("\n") + "\n"
+}
+
+fn join_numbers<T: ToString>(nums: &[T], sep: &str) -> String {
+    nums.iter().map(T::to_string).collect::<Vec<_>>().join(sep)
+}
+
+fn grid(rows: usize, cols: usize, mut cell: impl FnMut(usize, usize) -> char) -> String {
+    join_lines((0..rows).map(|row| (0..cols).map(|col| cell(row, col)).collect()))
+}
+
+fn location_lists(rng: &mut StdRng, size: usize) -> String {
+    let left: Vec<u32> = (0..size).map(|_| rng.random_range(10000..100000)).collect();
+    join_lines(left.iter().map(|l| {
+        // some right ids repeat left ones so that similarity scores aren't all zero
+        let r = if rng.random_bool(0.3) {
+            *left.choose(rng).unwrap()
+        } else {
+            rng.random_range(10000..100000)
+        };
+        format!("{l}   {r}")
+    }))
+}
+
+fn reports(rng: &mut StdRng, size: usize) -> String {
+    join_lines((0..size).map(|_| {
+        let len = rng.random_range(5..=8);
+        let sign = if rng.random_bool(0.5) { 1 } else { -1 };
+        let mut levels = vec![rng.random_range(10i64..90)];
+        for _ in 1..len {
+            // mostly safe steps, with the occasional flat, large or reversed one
+            let step = if rng.random_bool(0.1) {
+                *[0, 4, 5, -1, -2].choose(rng).unwrap()
+            } else {
+                rng.random_range(1..=3)
+            };
+            levels.push(levels.last().unwrap() + sign * step);
+        }
+
+        // shift the report up if it wandered below 1
+        let shift = (1 - levels.iter().min().unwrap()).max(0);
+        let levels: Vec<i64> = levels.iter().map(|l| l + shift).collect();
+        join_numbers(&levels, " ")
+    }))
+}
+
+fn corrupted_memory(rng: &mut StdRng, size: usize) -> String {
+    const JUNK: &[u8] = b"#$%&'()*+,-/:;<>?@[]^_{} abdilmnoprstuwhy0123456789";
+
+    let mut memory = String::new();
+    for _ in 0..size {
+        (0..rng.random_range(0..8)).for_each(|_| memory.push(*JUNK.choose(rng).unwrap() as char));
+
+        let (a, b) = (rng.random_range(1..1000), rng.random_range(1..1000));
+        let instruction = match rng.random_range(0..10) {
+            0 => String::from("do()"),
+            1 => String::from("don't()"),
+            2 => format!("mul({a},{b}]"),
+            3 => format!("mul[{a},{b})"),
+            4 => format!("mul({a}, {b})"),
+            _ => format!("mul({a},{b})"),
+        };
+        memory.push_str(&instruction);
+    }
+    memory + "\n"
+}
+
+fn word_search(rng: &mut StdRng, size: usize) -> String {
+    grid(size, size, |_, _| {
+        *['X', 'M', 'A', 'S'].choose(rng).unwrap()
+    })
+}
+
+fn print_queue(rng: &mut StdRng, size: usize) -> String {
+    // every pair of pages is ordered, consistently with a single hidden order
+    let mut order: Vec<u32> = (10..100).collect();
+    order.shuffle(rng);
+    order.truncate(49);
+
+    let mut rules: Vec<(u32, u32)> = order
+        .iter()
+        .enumerate()
+        .flat_map(|(i, a)| order[i + 1..].iter().map(|b| (*a, *b)))
+        .collect();
+    rules.shuffle(rng);
+
+    let updates = (0..size).map(|_| {
+        // updates have an odd length so that they have a middle page
+        let len = 2 * rng.random_range(2..=11) + 1;
+        let mut pages: Vec<usize> = (0..order.len()).collect();
+        pages.shuffle(rng);
+        pages.truncate(len);
+        if rng.random_bool(0.5) {
+            pages.sort();
+        }
+        let pages: Vec<u32> = pages.iter().map(|&i| order[i]).collect();
+        join_numbers(&pages, ",")
+    });
+
+    join_lines(rules.iter().map(|(a, b)| format!("{a}|{b}"))) + "\n" + &join_lines(updates)
+}
+
+/// How many moves a guard starting at `start` facing up makes before walking off the map, or
+/// `None` if they loop.
+fn patrol_length(obstructed: &[Vec<bool>], start: (usize, usize)) -> Option<usize> {
+    let size = obstructed.len() as i64;
+    let (mut row, mut col) = (start.0 as i64, start.1 as i64);
+    let (mut d_row, mut d_col) = (-1, 0);
+
+    // a guard that doesn't loop is never in the same cell facing the same way twice
+    for moves in 0..4 * obstructed.len().pow(2) {
+        let (next_row, next_col) = (row + d_row, col + d_col);
+        if !(0..size).contains(&next_row) || !(0..size).contains(&next_col) {
+            return Some(moves);
+        }
+        if obstructed[next_row as usize][next_col as usize] {
+            (d_row, d_col) = (d_col, -d_row);
+        } else {
+            (row, col) = (next_row, next_col);
+        }
+    }
+    None
+}
+
+fn guard_map(rng: &mut StdRng, size: usize) -> String {
+    // part 1 needs a map the guard leaves; of a few candidates keep the longest patrol
+    let (_, obstructed, start) = (0..)
+        .map(|_| {
+            let obstructed: Vec<Vec<bool>> = (0..size)
+                .map(|_| (0..size).map(|_| rng.random_bool(0.05)).collect())
+                .collect();
+            let start = (rng.random_range(0..size), rng.random_range(0..size));
+            let moves = match obstructed[start.0][start.1] {
+                true => None,
+                false => patrol_length(&obstructed, start),
+            };
+            (moves, obstructed, start)
+        })
+        .filter(|(moves, _, _)| moves.is_some())
+        .take(20)
+        .max_by_key(|(moves, _, _)| *moves)
+        .unwrap();
+
+    grid(size, size, |row, col| match (row, col) {
+        _ if (row, col) == start => '^',
+        _ if obstructed[row][col] => '#',
+        _ => '.',
+    })
+}
+
+fn calibrations(rng: &mut StdRng, size: usize) -> String {
+    join_lines((0..size).map(|_| {
+        // keep totals to at most 16 digits so that solvers can't overflow a u64
+        let len = rng.random_range(2..=8);
+        let max = if len <= 5 { 1000 } else { 100 };
+        let values: Vec<u64> = (0..len).map(|_| rng.random_range(1..max)).collect();
+
+        let mut total = values[1..]
+            .iter()
+            .fold(values[0], |acc, v| match rng.random_range(0..3) {
+                0 => acc + v,
+                1 => acc * v,
+                _ => format!("{acc}{v}").parse().unwrap(),
+            });
+        if rng.random_bool(0.5) {
+            total += rng.random_range(1..10);
+        }
+
+        format!("{total}: {}", join_numbers(&values, " "))
+    }))
+}
+
+fn antenna_map(rng: &mut StdRng, size: usize) -> String {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+    let mut cells = vec!['.'; size * size];
+    let mut free: Vec<usize> = (0..cells.len()).collect();
+    free.shuffle(rng);
+
+    let frequencies = (size * size / 50).clamp(1, FREQUENCIES.len());
+    for &frequency in &FREQUENCIES[..frequencies] {
+        for _ in 0..rng.random_range(3..=5) {
+            if let Some(i) = free.pop() {
+                cells[i] = frequency as char;
+            }
+        }
+    }
+
+    grid(size, size, |row, col| cells[row * size + col])
+}
+
+fn disk_map(rng: &mut StdRng, size: usize) -> String {
+    let mut map: String = (0..size)
+        .map(|_| {
+            let file = rng.random_range(1..=9u32);
+            let free = rng.random_range(0..=9u32);
+            format!("{file}{free}")
+        })
+        .collect();
+    // the map ends with a file, not free space
+    map.pop();
+    map + "\n"
+}
+
+fn topographic_map(rng: &mut StdRng, size: usize) -> String {
+    // heights mostly climb by one to the right and down, so that trails are plentiful
+    grid(size, size, |row, col| {
+        let height = if rng.random_bool(0.1) {
+            rng.random_range(0..10)
+        } else {
+            (row + col) as u32 % 10
+        };
+        char::from_digit(height, 10).unwrap()
+    })
+}
+
+fn stones(rng: &mut StdRng, size: usize) -> String {
+    let stones: Vec<u64> = (0..size).map(|_| rng.random_range(0..1_000_000)).collect();
+    join_numbers(&stones, " ") + "\n"
+}
+
+fn garden_plots(rng: &mut StdRng, size: usize) -> String {
+    let mut plots = vec![vec![b'A'; size]; size];
+    (0..size).for_each(|row| {
+        (0..size).for_each(|col| {
+            // plots mostly take the plant above or to the left, growing regions of all shapes
+            plots[row][col] = match rng.random_range(0..10) {
+                0..4 if row > 0 => plots[row - 1][col],
+                4..8 if col > 0 => plots[row][col - 1],
+                _ => rng.random_range(b'A'..=b'Z'),
+            };
+        })
+    });
+    grid(size, size, |row, col| char::from(plots[row][col]))
+}
+
+fn claw_machines(rng: &mut StdRng, size: usize) -> String {
+    let machines: Vec<String> = (0..size)
+        .map(|_| {
+            let a: (u32, u32) = (rng.random_range(10..100), rng.random_range(10..100));
+            let b: (u32, u32) = (rng.random_range(10..100), rng.random_range(10..100));
+            // about half the prizes can be won within 100 presses of each button
+            let prize = if rng.random_bool(0.5) {
+                let (pa, pb) = (rng.random_range(0..=100), rng.random_range(0..=100));
+                (pa * a.0 + pb * b.0, pa * a.1 + pb * b.1)
+            } else {
+                (rng.random_range(1000..20000), rng.random_range(1000..20000))
+            };
+            format!(
+                "Button A: X+{}, Y+{}\nButton B: X+{}, Y+{}\nPrize: X={}, Y={}\n",
+                a.0, a.1, b.0, b.1, prize.0, prize.1
+            )
+        })
+        .collect();
+    machines.join("\n")
+}
+
+fn robots(rng: &mut StdRng, size: usize) -> String {
+    // the real lobby, where half the robots come together in a block at one secret second
+    let (width, height) = (101, 103);
+    let seconds: i64 = rng.random_range(0..width * height);
+    let side = (size as f64 / 2.0).sqrt().ceil() as i64;
+    join_lines((0..size as i64).map(|i| {
+        let vel = (rng.random_range(-100..=100), rng.random_range(-100..=100));
+        let pos = if i % 2 == 0 && i / 2 < side * side {
+            let target = (40 + (i / 2) % side, 40 + (i / 2) / side);
+            (
+                (target.0 - vel.0 * seconds).rem_euclid(width),
+                (target.1 - vel.1 * seconds).rem_euclid(height),
+            )
+        } else {
+            (rng.random_range(0..width), rng.random_range(0..height))
+        };
+        format!("p={},{} v={},{}", pos.0, pos.1, vel.0, vel.1)
+    }))
+}
+
+fn warehouse(rng: &mut StdRng, size: usize) -> String {
+    // a walled map with the robot in the middle, then moves in lines of up to 1000 like the
+    // real input's 20000
+    let robot = (size / 2, size / 2);
+    let map = grid(size, size, |row, col| match (row, col) {
+        _ if (row, col) == robot => '@',
+        _ if row == 0 || col == 0 || row == size - 1 || col == size - 1 => '#',
+        _ => match rng.random_range(0..20) {
+            0 => '#',
+            1..8 => 'O',
+            _ => '.',
+        },
+    });
+    let moves: Vec<char> = (0..size * 400)
+        .map(|_| *['^', '>', 'v', '<'].choose(rng).unwrap())
+        .collect();
+    map + "\n" + &join_lines(moves.chunks(1000).map(|line| line.iter().collect()))
+}
+
+fn reindeer_maze(rng: &mut StdRng, size: usize) -> String {
+    // a maze carved between the odd rows and columns by a random depth-first walk, with a few
+    // more walls knocked out so that there are loops and paths that tie
+    let size = size.max(5);
+    let mut open = vec![vec![false; size]; size];
+    // the last odd row and column inside the outer wall
+    let last = if size.is_multiple_of(2) {
+        size - 3
+    } else {
+        size - 2
+    };
+    let mut stack: Vec<(usize, usize)> = vec![(1, 1)];
+    open[1][1] = true;
+    while let Some(&(row, col)) = stack.last() {
+        let next: Vec<(usize, usize)> = [(0, 2), (2, 0), (0, -2), (-2, 0)]
+            .iter()
+            .filter_map(|&(dr, dc)| {
+                let r = row.checked_add_signed(dr).filter(|&r| r <= last)?;
+                let c = col.checked_add_signed(dc).filter(|&c| c <= last)?;
+                (!open[r][c]).then_some((r, c))
+            })
+            .collect();
+        match next.choose(rng) {
+            Some(&(r, c)) => {
+                open[(row + r) / 2][(col + c) / 2] = true;
+                open[r][c] = true;
+                stack.push((r, c));
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+    (1..=last).for_each(|row| {
+        (1..=last).for_each(|col| {
+            if (row + col) % 2 == 1 && rng.random_bool(0.05) {
+                open[row][col] = true;
+            }
+        })
+    });
+
+    grid(size, size, |row, col| match (row, col) {
+        _ if (row, col) == (last, 1) => 'S',
+        _ if (row, col) == (1, last) => 'E',
+        _ if open[row][col] => '.',
+        _ => '#',
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solutions;
+
+    #[test]
+    fn test_generate() {
+        GENERATORS.iter().for_each(|g| {
+            let input = g.generate(1, 20);
+            solutions::find(g.day, None).iter().for_each(|s| {
+                let run = s.run(&input);
+                assert!(
+                    run.is_ok(),
+                    "day {} part {}: {:?}",
+                    g.day,
+                    s.part,
+                    run.err()
+                );
+            });
+        });
+    }
+
+    #[test]
+    fn test_generate_seeded() {
+        GENERATORS.iter().for_each(|g| {
+            assert_eq!(g.generate(7, 10), g.generate(7, 10));
+            assert_ne!(g.generate(7, 10), g.generate(8, 10));
+        });
+    }
+
+    #[test]
+    fn test_generate_size() {
+        assert_eq!(find(1).unwrap().generate(1, 30).lines().count(), 30);
+        assert_eq!(find(9).unwrap().generate(1, 30).trim_end().len(), 59);
+
+        let map = find(6).unwrap().generate(1, 12);
+        assert_eq!(map.lines().count(), 12);
+        assert!(map.lines().all(|l| l.len() == 12));
+        assert_eq!(map.matches('^').count(), 1);
+
+        let warehouse = find(15).unwrap().generate(1, 12);
+        let (map, moves) = warehouse.split_once("\n\n").unwrap();
+        assert!(map.lines().all(|l| l.len() == 12));
+        assert_eq!(map.matches('@').count(), 1);
+        assert_eq!(moves.lines().map(str::len).sum::<usize>(), 4800);
+
+        let maze = find(16).unwrap().generate(1, 12);
+        assert_eq!(maze.lines().count(), 12);
+        assert_eq!(
+            (maze.matches('S').count(), maze.matches('E').count()),
+            (1, 1)
+        );
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find(5).unwrap().day, 5);
+        assert!(find(25).is_none());
+    }
+}
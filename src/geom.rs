@@ -0,0 +1,213 @@
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A signed 2D coordinate, `x` growing to the right and `y` growing downwards.
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point::new(0, 0);
+
+    pub const fn new(x: i32, y: i32) -> Self {
+        Point { x, y }
+    }
+
+    /// The point at unsigned grid index `(row, col)`.
+    pub fn from_index(row: usize, col: usize) -> Self {
+        Point::new(col as i32, row as i32)
+    }
+
+    /// The unsigned grid index `(row, col)` of this point, if neither coordinate is negative.
+    pub fn index(&self) -> Option<(usize, usize)> {
+        if self.x < 0 || self.y < 0 {
+            None
+        } else {
+            Some((self.y as usize, self.x as usize))
+        }
+    }
+
+    pub fn manhattan(&self, other: &Point) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Point) -> u32 {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self + rhs.offset()
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i32> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i32) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/// One of the eight compass directions on a grid, where up is towards smaller `y`.
+#[derive(Default, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Copy, Debug)]
+pub enum Direction {
+    #[default]
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// The four orthogonal directions, clockwise from up.
+    pub const ORTHOGONAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All eight directions, clockwise from up.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn offset(&self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::DownRight => Point::new(1, 1),
+            Direction::Down => Point::new(0, 1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    fn turn(&self, eighths: usize) -> Direction {
+        let idx = Direction::ALL.iter().position(|d| d == self).unwrap();
+        Direction::ALL[(idx + eighths) % 8]
+    }
+
+    /// The direction a quarter turn clockwise from this one.
+    pub fn rotate_cw(&self) -> Direction {
+        self.turn(2)
+    }
+
+    /// The direction a quarter turn counter-clockwise from this one.
+    pub fn rotate_ccw(&self) -> Direction {
+        self.turn(6)
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn(4)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_operators() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 5);
+
+        assert_eq!(a + b, Point::new(4, 3));
+        assert_eq!(a - b, Point::new(2, -7));
+        assert_eq!(a * 3, Point::new(9, -6));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a + Direction::Up, Point::new(3, -3));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(3, -2);
+        let b = Point::new(1, 5);
+
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(a.chebyshev(&b), 7);
+        assert_eq!(a.manhattan(&a), 0);
+    }
+
+    #[test]
+    fn test_index_conversion() {
+        let p = Point::from_index(2, 7);
+        assert_eq!(p, Point::new(7, 2));
+        assert_eq!(p.index(), Some((2, 7)));
+        assert_eq!(Point::new(-1, 0).index(), None);
+    }
+
+    #[test]
+    fn test_rotation() {
+        assert_eq!(Direction::Up.rotate_cw(), Direction::Right);
+        assert_eq!(Direction::Left.rotate_cw(), Direction::Up);
+        assert_eq!(Direction::Up.rotate_ccw(), Direction::Left);
+        assert_eq!(Direction::UpRight.rotate_cw(), Direction::DownRight);
+        assert_eq!(Direction::DownLeft.opposite(), Direction::UpRight);
+
+        Direction::ALL.iter().for_each(|d| {
+            assert_eq!(d.rotate_cw().rotate_ccw(), *d);
+            assert_eq!(d.opposite().opposite(), *d);
+            assert_eq!(d.opposite().offset(), -d.offset());
+        });
+    }
+}
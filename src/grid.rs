@@ -0,0 +1,226 @@
+use crate::error::ParseError;
+use crate::geom::{Direction, Point};
+use std::fmt;
+use std::fmt::Formatter;
+
+/// A rectangular grid of cells stored in a flat, row-major buffer.
+#[derive(Default, PartialEq, Eq, Clone, Debug)]
+pub struct Grid<T> {
+    rows: usize,
+    cols: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Parses a block of text into a grid, one row per line, converting each character with `f`.
+    /// Fails on the first character `f` rejects or on a row whose length differs from the first.
+    pub fn parse(input: &str, f: impl FnMut(char) -> Option<T>) -> Result<Self, ParseError> {
+        let mut f = f;
+        let mut rows = 0;
+        let mut cols = 0;
+        let mut cells = vec![];
+
+        for (i, line) in input.lines().enumerate() {
+            for (offset, c) in line.char_indices() {
+                let cell = f(c).ok_or_else(|| {
+                    ParseError::at(
+                        i,
+                        line,
+                        &line[offset..offset + c.len_utf8()],
+                        "unexpected character",
+                    )
+                })?;
+                cells.push(cell);
+            }
+
+            let len = line.chars().count();
+            if i == 0 {
+                cols = len;
+            } else if len != cols {
+                let message = format!("expected a row of {cols} cells, found {len}");
+                return Err(ParseError::at(i, line, line, &message));
+            }
+            rows += 1;
+        }
+
+        Ok(Grid { rows, cols, cells })
+    }
+
+    /// The number of rows and columns in the grid.
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.rows, self.cols)
+    }
+
+    pub fn contains(&self, pos: Point) -> bool {
+        self.index(pos).is_some()
+    }
+
+    fn index(&self, pos: Point) -> Option<usize> {
+        pos.index()
+            .filter(|&(i, j)| i < self.rows && j < self.cols)
+            .map(|(i, j)| i * self.cols + j)
+    }
+
+    fn position(&self, idx: usize) -> Point {
+        Point::from_index(idx / self.cols, idx % self.cols)
+    }
+
+    pub fn get(&self, pos: Point) -> Option<&T> {
+        self.index(pos).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, pos: Point) -> Option<&mut T> {
+        self.index(pos).map(|idx| &mut self.cells[idx])
+    }
+
+    /// A grid of the same shape with each cell converted by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// Every cell along with its position, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.position(idx), cell))
+    }
+
+    /// The in-bounds positions up, right, down and left of `pos`.
+    pub fn neighbors4(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(pos, &Direction::ORTHOGONAL)
+    }
+
+    /// The in-bounds positions surrounding `pos`, clockwise from up.
+    pub fn neighbors8(&self, pos: Point) -> impl Iterator<Item = Point> + '_ {
+        self.neighbors(pos, &Direction::ALL)
+    }
+
+    fn neighbors<'a>(
+        &'a self,
+        pos: Point,
+        directions: &'static [Direction],
+    ) -> impl Iterator<Item = Point> + 'a {
+        directions
+            .iter()
+            .map(move |&d| pos + d)
+            .filter(|p| self.contains(*p))
+    }
+
+    /// The positions of every cell matching `pred`, in row-major order.
+    pub fn positions<'a>(
+        &'a self,
+        mut pred: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter_map(move |(pos, cell)| if pred(cell) { Some(pos) } else { None })
+    }
+
+    /// The position of the first cell matching `pred`, in row-major order.
+    pub fn find(&self, pred: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.positions(pred).next()
+    }
+}
+
+impl fmt::Display for Grid<char> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.cells.chunks(self.cols.max(1)).try_for_each(|row| {
+            let line: String = row.iter().collect();
+            writeln!(f, "{line}")
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use indoc::indoc;
+
+    const TEST_INPUT: &str = indoc! {"
+        abc
+        def
+    "};
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse(TEST_INPUT, Some).unwrap();
+        assert_eq!(grid.dimensions(), (2, 3));
+        assert_eq!(grid.get(Point::new(0, 0)), Some(&'a'));
+        assert_eq!(grid.get(Point::new(2, 1)), Some(&'f'));
+        assert_eq!(grid.to_string(), TEST_INPUT);
+    }
+
+    #[test]
+    fn test_parse_malformed() {
+        let err = Grid::parse("abc\ndXf\n", |c| c.is_lowercase().then_some(c)).unwrap_err();
+        assert_eq!(err, ParseError::new(2, 2, "X", "unexpected character"));
+
+        let err = Grid::parse("abc\nde\n", Some).unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::new(2, 1, "de", "expected a row of 3 cells, found 2")
+        );
+    }
+
+    #[test]
+    fn test_get_out_of_bounds() {
+        let grid = Grid::parse(TEST_INPUT, Some).unwrap();
+        assert_eq!(grid.get(Point::new(0, -1)), None);
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 2)), None);
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut grid = Grid::parse(TEST_INPUT, Some).unwrap();
+        *grid.get_mut(Point::new(1, 1)).unwrap() = 'x';
+        assert_eq!(grid.get(Point::new(1, 1)), Some(&'x'));
+        assert_eq!(grid.get_mut(Point::new(5, 5)), None);
+    }
+
+    #[test]
+    fn test_map() {
+        let grid = Grid::parse(TEST_INPUT, Some)
+            .unwrap()
+            .map(|c| c.to_ascii_uppercase());
+        assert_eq!(grid.to_string(), "ABC\nDEF\n");
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::parse(TEST_INPUT, Some).unwrap();
+
+        let corner: Vec<Point> = grid.neighbors4(Point::new(0, 0)).collect();
+        assert_eq!(corner, vec![Point::new(1, 0), Point::new(0, 1)]);
+
+        let middle: Vec<Point> = grid.neighbors8(Point::new(1, 0)).collect();
+        assert_eq!(
+            middle,
+            vec![
+                Point::new(2, 0),
+                Point::new(2, 1),
+                Point::new(1, 1),
+                Point::new(0, 1),
+                Point::new(0, 0)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_and_positions() {
+        let grid = Grid::parse(TEST_INPUT, |c| c.to_digit(16)).unwrap();
+        assert_eq!(grid.find(|&v| v == 0xe), Some(Point::new(1, 1)));
+        assert_eq!(grid.find(|&v| v == 0), None);
+
+        let evens: Vec<Point> = grid.positions(|v| v % 2 == 0).collect();
+        assert_eq!(
+            evens,
+            vec![Point::new(0, 0), Point::new(2, 0), Point::new(1, 1)]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+use std::borrow::Cow;
+use std::collections::VecDeque;
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::str::FromStr;
+
+/// Puzzle input as every generator expects it: no byte order mark, `\n` line endings, no
+/// trailing whitespace on any line and no trailing blank lines. Input that is already like that
+/// is borrowed as is.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input).trim_end();
+    let trimmed = input
+        .lines()
+        .all(|line| line.len() == line.trim_end().len());
+    match trimmed && !input.contains("\r\n") {
+        true => Cow::Borrowed(input),
+        false => Cow::Owned(
+            input
+                .lines()
+                .map(str::trim_end)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        ),
+    }
+}
+
+/// The lines of `reader`, read one at a time, exactly as `normalize(input).lines()` would give
+/// them for the whole input.
+pub fn lines<R: BufRead>(reader: R) -> Lines<R> {
+    Lines {
+        lines: reader.lines(),
+        first: true,
+        ready: VecDeque::new(),
+        blanks: vec![],
+    }
+}
+
+/// An iterator over normalized lines, see [`lines`].
+#[derive(Debug)]
+pub struct Lines<R> {
+    lines: io::Lines<R>,
+    first: bool,
+    /// Lines known not to be at the end of the input.
+    ready: VecDeque<String>,
+    /// Blank lines not yet followed by anything else, dropped if nothing does.
+    blanks: Vec<String>,
+}
+
+impl<R: BufRead> Iterator for Lines<R> {
+    type Item = io::Result<String>;
+
+    fn next(&mut self) -> Option<io::Result<String>> {
+        loop {
+            if let Some(line) = self.ready.pop_front() {
+                return Some(Ok(line));
+            }
+
+            let mut line = match self.lines.next()? {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if std::mem::take(&mut self.first) && line.starts_with('\u{feff}') {
+                line.remove(0);
+            }
+            line.truncate(line.trim_end().len());
+
+            if line.is_empty() {
+                self.blanks.push(line);
+            } else {
+                self.ready.extend(self.blanks.drain(..));
+                self.ready.push_back(line);
+            }
+        }
+    }
+}
+
+/// The whole of the file at `path`, or of stdin when `path` is `-`, the way the binaries take
+/// their input.
+pub fn read(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input)?;
+        Ok(input)
+    } else {
+        fs::read_to_string(path)
+    }
+}
+
+/// A binary's command line arguments, where a `--flag` can take the argument after it as its
+/// value.
+#[derive(Debug)]
+pub struct Flags<I> {
+    args: I,
+}
+
+impl<I: Iterator<Item = String>> Flags<I> {
+    pub fn new(args: I) -> Self {
+        Flags { args }
+    }
+
+    /// The argument after `flag`.
+    pub fn value(&mut self, flag: &str) -> Result<String, String> {
+        self.args
+            .next()
+            .ok_or_else(|| format!("missing value for {flag}"))
+    }
+
+    /// The argument after `flag` parsed as a `T`, with `what` naming it if it doesn't parse.
+    pub fn parse<T: FromStr>(&mut self, flag: &str, what: &str) -> Result<T, String> {
+        parse_arg(&self.value(flag)?, what)
+    }
+}
+
+impl<I: Iterator<Item = String>> Iterator for Flags<I> {
+    type Item = String;
+
+    fn next(&mut self) -> Option<String> {
+        self.args.next()
+    }
+}
+
+/// `arg` parsed as a `T`, with `what` naming it if it doesn't parse.
+pub fn parse_arg<T: FromStr>(arg: &str, what: &str) -> Result<T, String> {
+    arg.parse().map_err(|_| format!("invalid {what} {arg:?}"))
+}
+
+/// `input` as a Windows editor might save it, with a byte order mark and CRLF line endings.
+#[cfg(test)]
+pub(crate) fn windows_style(input: &str) -> String {
+    format!("\u{feff}{}\r\n", input.trim_end().replace('\n', "\r\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("1 2\r\n\r\n3 4\r\n"), "1 2\n\n3 4");
+        assert_eq!(normalize("\u{feff}1 2\n3 4\n\n  \n"), "1 2\n3 4");
+        assert_eq!(normalize("\u{feff}"), "");
+        assert_eq!(normalize("a\rb"), "a\rb");
+        assert_eq!(normalize("1 2 \n"), "1 2");
+        assert_eq!(normalize("1 2 \n3 4\t\n"), "1 2\n3 4");
+    }
+
+    #[test]
+    fn test_normalize_borrows() {
+        assert!(matches!(normalize("1 2\n3 4\n"), Cow::Borrowed("1 2\n3 4")));
+        assert!(matches!(normalize("1 2\r\n"), Cow::Borrowed("1 2")));
+        assert!(matches!(normalize("1 2\r\n3 4"), Cow::Owned(_)));
+        assert!(matches!(normalize("1 2 \n3 4"), Cow::Owned(_)));
+    }
+
+    #[test]
+    fn test_lines() {
+        let inputs = [
+            "",
+            "\n\n",
+            "1 2\n3 4",
+            "\u{feff}1 2\r\n\r\n  \r\n3 4 \r\n\t\r\n\n",
+            "\n\n1 2\n\n",
+            "a\r\r\nb\r\r\r\n\r",
+            "\u{feff}",
+        ];
+        inputs.iter().for_each(|input| {
+            let streamed: Vec<String> = lines(input.as_bytes()).map(Result::unwrap).collect();
+            let normalized = normalize(input);
+            let expected: Vec<&str> = normalized.lines().collect();
+            assert_eq!(streamed, expected, "{input:?}");
+        });
+    }
+
+    proptest! {
+        #[test]
+        fn test_lines_matches_normalize(input in "[ab \t\r\n\u{feff}]{0,40}") {
+            let streamed: Vec<String> = lines(input.as_bytes()).map(Result::unwrap).collect();
+            let normalized = normalize(&input);
+            prop_assert_eq!(streamed, normalized.lines().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_lines_io_error() {
+        let mut lines = lines(&b"1 2\n\xff\n"[..]);
+        assert!(lines.any(|line| line.is_err()));
+    }
+
+    #[test]
+    fn test_read() {
+        let err = read("no/such/input.txt").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_flags() {
+        let mut flags = Flags::new("9 --part 2 --seed x --output".split(' ').map(String::from));
+        assert_eq!(flags.next().as_deref(), Some("9"));
+        assert_eq!(flags.next().as_deref(), Some("--part"));
+        assert_eq!(flags.parse::<u8>("--part", "part"), Ok(2));
+        assert_eq!(flags.next().as_deref(), Some("--seed"));
+        assert_eq!(
+            flags.parse::<u64>("--seed", "seed"),
+            Err(String::from("invalid seed \"x\""))
+        );
+        assert_eq!(flags.next().as_deref(), Some("--output"));
+        assert_eq!(
+            flags.value("--output"),
+            Err(String::from("missing value for --output"))
+        );
+        assert_eq!(parse_arg::<u8>("12", "day"), Ok(12));
+    }
+
+    #[test]
+    fn test_windows_style() {
+        assert_eq!(windows_style("1 2\n3 4\n"), "\u{feff}1 2\r\n3 4\r\n");
+        assert_eq!(normalize(&windows_style("1 2\n\n3 4\n")), "1 2\n\n3 4");
+    }
+}
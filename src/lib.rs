@@ -1,14 +1,50 @@
+#[cfg(feature = "day01")]
 mod day_01;
+#[cfg(feature = "day02")]
 mod day_02;
+#[cfg(feature = "day03")]
 mod day_03;
+#[cfg(feature = "day04")]
 mod day_04;
+#[cfg(feature = "day05")]
 mod day_05;
+#[cfg(feature = "day06")]
 mod day_06;
+#[cfg(feature = "day07")]
 mod day_07;
+#[cfg(feature = "day08")]
 mod day_08;
+#[cfg(feature = "day09")]
 mod day_09;
+#[cfg(feature = "day10")]
 mod day_10;
+#[cfg(feature = "day11")]
 mod day_11;
+#[cfg(feature = "day12")]
+mod day_12;
+#[cfg(feature = "day13")]
+mod day_13;
+#[cfg(feature = "day14")]
+mod day_14;
+#[cfg(feature = "day15")]
+mod day_15;
+#[cfg(feature = "day16")]
+mod day_16;
+
+pub mod alloc_stats;
+pub mod animate;
+#[cfg(feature = "client")]
+pub mod client;
+pub mod error;
+pub mod export;
+pub mod gen;
+pub mod geom;
+pub mod grid;
+pub mod input;
+pub mod parallel;
+pub mod render;
+pub mod report;
+pub mod solutions;
 
 use aoc_runner_derive::aoc_lib;
 
@@ -0,0 +1,70 @@
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use std::iter::Sum;
+
+/// How to run a loop over independent items: in order on this thread, or spread across a rayon
+/// thread pool when built with the `parallel` feature. Both produce identical results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Serial,
+    #[cfg(feature = "parallel")]
+    Parallel,
+}
+
+impl Default for Mode {
+    /// Parallel when the `parallel` feature is enabled, serial otherwise.
+    fn default() -> Self {
+        #[cfg(feature = "parallel")]
+        return Mode::Parallel;
+
+        #[cfg(not(feature = "parallel"))]
+        return Mode::Serial;
+    }
+}
+
+impl Mode {
+    /// The number of `items` matching `pred`.
+    pub fn count<T: Sync>(self, items: &[T], pred: impl Fn(&T) -> bool + Sync + Send) -> usize {
+        match self {
+            Mode::Serial => items.iter().filter(|item| pred(item)).count(),
+            #[cfg(feature = "parallel")]
+            Mode::Parallel => items.par_iter().filter(|item| pred(item)).count(),
+        }
+    }
+
+    /// The sum of `f` over `items`.
+    pub fn sum<T: Sync, S: Sum + Send>(self, items: &[T], f: impl Fn(&T) -> S + Sync + Send) -> S {
+        match self {
+            Mode::Serial => items.iter().map(f).sum(),
+            #[cfg(feature = "parallel")]
+            Mode::Parallel => items.par_iter().map(f).sum(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serial() {
+        let items: Vec<u64> = (1..=100).collect();
+        assert_eq!(Mode::Serial.count(&items, |i| i % 3 == 0), 33);
+        assert_eq!(Mode::Serial.sum(&items, |i| *i), 5050);
+    }
+
+    #[test]
+    #[cfg(feature = "parallel")]
+    fn test_parallel_matches_serial() {
+        let items: Vec<u64> = (1..=10_000).collect();
+        assert_eq!(Mode::default(), Mode::Parallel);
+        assert_eq!(
+            Mode::Parallel.count(&items, |i| i % 7 == 0),
+            Mode::Serial.count(&items, |i| i % 7 == 0)
+        );
+        assert_eq!(
+            Mode::Parallel.sum(&items, |i| i * i),
+            Mode::Serial.sum(&items, |i| i * i)
+        );
+    }
+}
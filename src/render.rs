@@ -0,0 +1,241 @@
+#[cfg(feature = "day04")]
+use crate::day_04;
+#[cfg(feature = "day06")]
+use crate::day_06;
+#[cfg(feature = "day08")]
+use crate::day_08;
+#[cfg(feature = "day10")]
+use crate::day_10;
+use crate::error::ParseError;
+use crate::geom::Point;
+use crate::grid::Grid;
+use std::io;
+use std::path::Path;
+
+/// A colour as red, green and blue components.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+
+    /// A shade of grey, from black at 0 to white at 255.
+    pub fn grey(level: u8) -> Rgb {
+        Rgb(level, level, level)
+    }
+
+    /// One of a fixed set of distinct colours, for telling categories apart.
+    pub fn palette(i: usize) -> Rgb {
+        const PALETTE: [Rgb; 8] = [
+            Rgb(230, 25, 75),
+            Rgb(60, 180, 75),
+            Rgb(255, 225, 25),
+            Rgb(0, 130, 200),
+            Rgb(245, 130, 48),
+            Rgb(145, 30, 180),
+            Rgb(70, 240, 240),
+            Rgb(240, 50, 230),
+        ];
+        PALETTE[i % PALETTE.len()]
+    }
+}
+
+/// An image in memory, stored row-major.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// An image with one pixel per cell of `grid`, coloured by `color`.
+    pub fn from_grid<T>(grid: &Grid<T>, mut color: impl FnMut(Point, &T) -> Rgb) -> Self {
+        let (height, width) = grid.dimensions();
+        Image {
+            width,
+            height,
+            pixels: grid
+                .iter()
+                .map(|(point, cell)| color(point, cell))
+                .collect(),
+        }
+    }
+
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    /// The pixel at column `x` and row `y`.
+    pub fn pixel(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// The image with every pixel blown up to a `factor` by `factor` square.
+    pub fn scaled(&self, factor: usize) -> Self {
+        let (width, height) = (self.width * factor, self.height * factor);
+        let pixels = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x / factor, y / factor)))
+            .map(|(x, y)| self.pixels[y * self.width + x])
+            .collect();
+
+        Image {
+            width,
+            height,
+            pixels,
+        }
+    }
+
+    fn rgb_bytes(&self) -> Vec<u8> {
+        self.pixels
+            .iter()
+            .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+            .collect()
+    }
+
+    /// The image as a binary PPM (P6) file.
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut ppm = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        ppm.extend(self.rgb_bytes());
+        ppm
+    }
+
+    /// The image as a PNG file.
+    #[cfg(feature = "png")]
+    pub fn to_png(&self) -> Vec<u8> {
+        let mut png = vec![];
+        let mut encoder = png::Encoder::new(&mut png, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+
+        // writing to a Vec can't fail, and the data always matches the header
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&self.rgb_bytes()).unwrap();
+        writer.finish().unwrap();
+        png
+    }
+
+    /// Writes the image to `path` as PNG if it ends in `.png`, otherwise as PPM.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let bytes = match path.extension().and_then(|e| e.to_str()) {
+            #[cfg(feature = "png")]
+            Some("png") => self.to_png(),
+            #[cfg(not(feature = "png"))]
+            Some("png") => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    "PNG output needs the `png` feature",
+                ))
+            }
+            _ => self.to_ppm(),
+        };
+        std::fs::write(path, bytes)
+    }
+}
+
+/// The days that can be rendered.
+pub const DAYS: &[u8] = &[
+    #[cfg(feature = "day04")]
+    4,
+    #[cfg(feature = "day06")]
+    6,
+    #[cfg(feature = "day08")]
+    8,
+    #[cfg(feature = "day10")]
+    10,
+];
+
+/// Parses `input` for `day` and renders it, or `None` if the day can't be rendered.
+#[cfg_attr(
+    not(any(
+        feature = "day04",
+        feature = "day06",
+        feature = "day08",
+        feature = "day10"
+    )),
+    allow(unused_variables)
+)]
+pub fn render(day: u8, input: &str) -> Option<Result<Image, ParseError>> {
+    match day {
+        #[cfg(feature = "day04")]
+        4 => Some(day_04::input_generator(input).map(|puzzle| day_04::render(&puzzle))),
+        #[cfg(feature = "day06")]
+        6 => Some(day_06::input_generator(input).map(|map| day_06::render(&map))),
+        #[cfg(feature = "day08")]
+        8 => Some(day_08::input_generator(input).map(|map| day_08::render(&map))),
+        #[cfg(feature = "day10")]
+        10 => Some(day_10::input_generator(input).map(|map| day_10::render(&map))),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn image() -> Image {
+        let grid = Grid::parse("ab\ncd\nef", Some).unwrap();
+        Image::from_grid(&grid, |point, &c| match c {
+            'a' => Rgb::WHITE,
+            _ => Rgb(point.x as u8, point.y as u8, 7),
+        })
+    }
+
+    #[test]
+    fn test_from_grid() {
+        let image = image();
+        assert_eq!(image.dimensions(), (2, 3));
+        assert_eq!(image.pixel(0, 0), Some(Rgb::WHITE));
+        assert_eq!(image.pixel(1, 2), Some(Rgb(1, 2, 7)));
+        assert_eq!(image.pixel(2, 0), None);
+    }
+
+    #[test]
+    fn test_scaled() {
+        let image = image().scaled(3);
+        assert_eq!(image.dimensions(), (6, 9));
+        assert_eq!(image.pixel(2, 2), Some(Rgb::WHITE));
+        assert_eq!(image.pixel(3, 2), Some(Rgb(1, 0, 7)));
+        assert_eq!(image.pixel(5, 8), Some(Rgb(1, 2, 7)));
+    }
+
+    #[test]
+    fn test_to_ppm() {
+        let ppm = image().to_ppm();
+        let header = b"P6\n2 3\n255\n";
+        assert_eq!(&ppm[..header.len()], header);
+        assert_eq!(ppm.len(), header.len() + 2 * 3 * 3);
+        assert_eq!(
+            &ppm[header.len()..header.len() + 6],
+            &[255, 255, 255, 1, 0, 7]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "png")]
+    fn test_to_png() {
+        let png = image().to_png();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+
+        let decoder = png::Decoder::new(std::io::Cursor::new(png));
+        let mut reader = decoder.read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size().unwrap()];
+        reader.next_frame(&mut pixels).unwrap();
+        assert_eq!(&pixels[..6], &[255, 255, 255, 1, 0, 7]);
+    }
+
+    #[test]
+    fn test_render() {
+        assert!(render(5, "").is_none());
+        DAYS.iter().for_each(|&day| {
+            let example = crate::solutions::find(day, None)[0].example;
+            let image = render(day, example).unwrap().unwrap();
+            let grid_size = (
+                example.lines().next().unwrap().len(),
+                example.lines().count(),
+            );
+            assert_eq!(image.dimensions(), grid_size);
+        });
+    }
+}
@@ -0,0 +1,185 @@
+use crate::solutions::{Run, Solution};
+use serde::Serialize;
+use std::fmt::Display;
+use std::io::BufRead;
+
+/// The outcome of running one part of one day.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub struct PartReport {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    pub generator_ns: u64,
+    pub solver_ns: u64,
+    // allocation counts, bytes and peaks are only recorded with the `alloc-stats` feature
+    pub generator_allocs: Option<u64>,
+    pub generator_alloc_bytes: Option<u64>,
+    pub generator_peak_bytes: Option<u64>,
+    pub solver_allocs: Option<u64>,
+    pub solver_alloc_bytes: Option<u64>,
+    pub solver_peak_bytes: Option<u64>,
+    /// Most bytes live at once across both phases, including the generator's output while
+    /// the solver runs.
+    pub peak_alloc_bytes: Option<u64>,
+}
+
+impl PartReport {
+    pub fn run(solution: &Solution, input: &str) -> Self {
+        Self::new(solution, solution.run(input))
+    }
+
+    /// Runs `solution` on the day's example.
+    pub fn run_example(solution: &Solution) -> Self {
+        Self::new(solution, solution.run_example())
+    }
+
+    /// Runs `solution` on input from `reader`, streaming it when the day supports that.
+    pub fn run_reader(solution: &Solution, reader: &mut dyn BufRead) -> Self {
+        Self::new(solution, solution.run_reader(reader))
+    }
+
+    fn new(solution: &Solution, run: Result<Run, impl Display>) -> Self {
+        let mut report = PartReport {
+            day: solution.day,
+            part: solution.part,
+            ..Default::default()
+        };
+
+        match run {
+            Ok(run) => {
+                report.answer = Some(run.answer);
+                report.generator_ns = run.generator.as_nanos() as u64;
+                report.solver_ns = run.solver.as_nanos() as u64;
+
+                if let Some(stats) = run.generator_alloc {
+                    report.generator_allocs = Some(stats.allocations);
+                    report.generator_alloc_bytes = Some(stats.bytes);
+                    report.generator_peak_bytes = Some(stats.peak);
+                }
+                if let Some(stats) = run.solver_alloc {
+                    report.solver_allocs = Some(stats.allocations);
+                    report.solver_alloc_bytes = Some(stats.bytes);
+                    report.solver_peak_bytes = Some(stats.peak);
+                }
+                if let (Some(generator), Some(solver)) = (run.generator_alloc, run.solver_alloc) {
+                    report.peak_alloc_bytes =
+                        Some(generator.peak.max(generator.retained + solver.peak));
+                }
+            }
+            Err(e) => report.error = Some(e.to_string()),
+        }
+
+        report
+    }
+}
+
+/// The results of a run over any number of days, serializable for diffing and charting.
+#[derive(Serialize, Debug, Clone, PartialEq, Default)]
+pub struct Report {
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn push(&mut self, part: PartReport) {
+        self.parts.push(part);
+    }
+
+    pub fn has_errors(&self) -> bool {
+        self.parts.iter().any(|p| p.error.is_some())
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// One row per part with a header, leaving missing values empty.
+    pub fn to_csv(&self) -> csv::Result<String> {
+        let mut writer = csv::Writer::from_writer(vec![]);
+        self.parts.iter().try_for_each(|p| writer.serialize(p))?;
+        let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+
+        Ok(String::from_utf8_lossy(&bytes).into_owned())
+    }
+}
+
+// the tests run days 9 and 11
+#[cfg(all(test, feature = "day09", feature = "day11"))]
+mod tests {
+    use super::*;
+    use crate::solutions;
+
+    fn report() -> Report {
+        let mut report = Report::default();
+        solutions::find(9, None)
+            .iter()
+            .for_each(|s| report.push(PartReport::run(s, s.example)));
+        solutions::find(11, Some(1))
+            .iter()
+            .for_each(|s| report.push(PartReport::run(s, "125 x")));
+
+        // timings and allocations vary between runs and builds
+        report.parts.iter_mut().for_each(|p| {
+            *p = PartReport {
+                day: p.day,
+                part: p.part,
+                answer: p.answer.clone(),
+                error: p.error.clone(),
+                generator_ns: 10,
+                solver_ns: 20,
+                ..Default::default()
+            }
+        });
+        report
+    }
+
+    #[test]
+    fn test_run() {
+        let report = report();
+        assert_eq!(report.parts.len(), 3);
+        assert_eq!(report.parts[1].answer.as_deref(), Some("2858"));
+        assert_eq!(
+            report.parts[2].error.as_deref(),
+            Some("line 1, column 5: expected a number (found \"x\")")
+        );
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn test_run_alloc_stats() {
+        let solution = solutions::find(11, Some(2))[0];
+        let part = PartReport::run(&solution, solution.example);
+
+        assert!(part.generator_allocs.unwrap() > 0);
+        assert!(part.solver_alloc_bytes.unwrap() > 0);
+        assert!(part.peak_alloc_bytes.unwrap() >= part.solver_peak_bytes.unwrap());
+    }
+
+    #[test]
+    fn test_to_json() {
+        let json: serde_json::Value = serde_json::from_str(&report().to_json().unwrap()).unwrap();
+        assert_eq!(json["parts"][0]["day"], 9);
+        assert_eq!(json["parts"][0]["answer"], "1928");
+        assert_eq!(json["parts"][0]["error"], serde_json::Value::Null);
+        assert_eq!(json["parts"][2]["answer"], serde_json::Value::Null);
+        assert_eq!(json["parts"][2]["solver_ns"], 20);
+    }
+
+    #[test]
+    fn test_to_csv() {
+        let csv = report().to_csv().unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines,
+            vec![
+                "day,part,answer,error,generator_ns,solver_ns,\
+                 generator_allocs,generator_alloc_bytes,generator_peak_bytes,\
+                 solver_allocs,solver_alloc_bytes,solver_peak_bytes,peak_alloc_bytes",
+                "9,1,1928,,10,20,,,,,,,",
+                "9,2,2858,,10,20,,,,,,,",
+                "11,1,,\"line 1, column 5: expected a number (found \"\"x\"\")\",10,20,,,,,,,",
+            ]
+        );
+    }
+}
@@ -0,0 +1,357 @@
+use crate::alloc_stats::{self, AllocStats};
+use crate::error::{ParseError, ReadError};
+use std::io::BufRead;
+use std::time::{Duration, Instant};
+
+/// The answer to one part of a day along with how long each phase took.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Run {
+    pub answer: String,
+    pub generator: Duration,
+    pub solver: Duration,
+    /// Heap usage of each phase, when built with the `alloc-stats` feature.
+    pub generator_alloc: Option<AllocStats>,
+    pub solver_alloc: Option<AllocStats>,
+}
+
+/// A solver with its generator already run, ready to produce the answer.
+pub type Prepared = Box<dyn Fn() -> String>;
+
+/// A reader-based generator, producing the same solver as `prepare` without loading the input.
+pub type Reader = fn(&mut dyn BufRead) -> Result<Prepared, ReadError>;
+
+/// A registered `#[aoc]` solver together with the generator that feeds it.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub day: u8,
+    pub part: u8,
+    /// The example input from the day's puzzle description.
+    pub example: &'static str,
+    prepare: fn(&str) -> Result<Prepared, ParseError>,
+    /// `prepare` for the example, which some days solve with other parameters than the real
+    /// input, like day 14's smaller lobby.
+    prepare_example: fn(&str) -> Result<Prepared, ParseError>,
+    /// Only for days that can parse their input as it streams in.
+    read: Option<Reader>,
+}
+
+impl Solution {
+    /// Runs only the generator on `input`, and returns the solver bound to its output.
+    pub fn prepare(&self, input: &str) -> Result<Prepared, ParseError> {
+        (self.prepare)(input)
+    }
+
+    /// Runs only the generator on the day's example, and returns the solver bound to its output.
+    pub fn prepare_example(&self) -> Result<Prepared, ParseError> {
+        (self.prepare_example)(self.example)
+    }
+
+    /// Whether the day has a generator that reads its input without loading all of it.
+    pub fn streams(&self) -> bool {
+        self.read.is_some()
+    }
+
+    /// Generates and solves `input`, timing each phase.
+    pub fn run(&self, input: &str) -> Result<Run, ParseError> {
+        Self::timed(|| self.prepare(input))
+    }
+
+    /// Like `run`, on the day's example.
+    pub fn run_example(&self) -> Result<Run, ParseError> {
+        Self::timed(|| self.prepare_example())
+    }
+
+    /// Like `run`, but generates from `reader`, streaming it when the day supports that and
+    /// reading it all first otherwise. The generator timing includes the reading.
+    pub fn run_reader(&self, reader: &mut dyn BufRead) -> Result<Run, ReadError> {
+        Self::timed(|| match self.read {
+            Some(read) => read(reader),
+            None => {
+                let mut input = String::new();
+                reader.read_to_string(&mut input)?;
+                Ok(self.prepare(&input)?)
+            }
+        })
+    }
+
+    fn timed<E>(prepare: impl FnOnce() -> Result<Prepared, E>) -> Result<Run, E> {
+        let start = Instant::now();
+        let (solver, generator_alloc) = alloc_stats::measure(prepare);
+        let solver = solver?;
+        let generator = start.elapsed();
+
+        let start = Instant::now();
+        let (answer, solver_alloc) = alloc_stats::measure(&solver);
+        let solver = start.elapsed();
+
+        Ok(Run {
+            answer,
+            generator,
+            solver,
+            generator_alloc,
+            solver_alloc,
+        })
+    }
+}
+
+// unused in a build without any day features
+#[allow(unused_macros)]
+macro_rules! solution {
+    ($day:literal, $part:literal, $module:ident, $generator:ident, $solver:ident) => {
+        solution!(@ $day, $part, $module, $generator, $generator, $solver, None)
+    };
+    (
+        $day:literal, $part:literal, $module:ident, $generator:ident, $solver:ident,
+        example = $example_generator:ident
+    ) => {
+        solution!(@ $day, $part, $module, $generator, $example_generator, $solver, None)
+    };
+    ($day:literal, $part:literal, $module:ident, $generator:ident, $solver:ident, $reader:ident) => {
+        solution!(@ $day, $part, $module, $generator, $generator, $solver, Some(|reader| {
+            let generated = crate::$module::$reader(reader)?;
+            Ok(Box::new(move || {
+                crate::$module::$solver(&generated).to_string()
+            }))
+        }))
+    };
+    (@prepare $module:ident, $generator:ident, $solver:ident) => {
+        |input| {
+            let generated = crate::$module::$generator(input)?;
+            Ok(Box::new(move || {
+                crate::$module::$solver(&generated).to_string()
+            }))
+        }
+    };
+    (
+        @ $day:literal, $part:literal, $module:ident, $generator:ident,
+        $example_generator:ident, $solver:ident, $read:expr
+    ) => {
+        Solution {
+            day: $day,
+            part: $part,
+            example: crate::$module::EXAMPLE,
+            prepare: solution!(@prepare $module, $generator, $solver),
+            prepare_example: solution!(@prepare $module, $example_generator, $solver),
+            read: $read,
+        }
+    };
+}
+
+/// Every implemented part, ordered by day then part.
+pub const SOLUTIONS: &[Solution] = &[
+    #[cfg(feature = "day01")]
+    solution!(1, 1, day_01, input_generator, solve_part1, read_input),
+    #[cfg(feature = "day01")]
+    solution!(1, 2, day_01, input_generator, solve_part2, read_input),
+    #[cfg(feature = "day02")]
+    solution!(2, 1, day_02, input_generator, part1, read_input),
+    #[cfg(feature = "day02")]
+    solution!(2, 2, day_02, input_generator, part2, read_input),
+    #[cfg(feature = "day03")]
+    solution!(3, 1, day_03, input_generator_one, solution, read_input_one),
+    #[cfg(feature = "day03")]
+    solution!(3, 2, day_03, input_generator_two, solution, read_input_two),
+    #[cfg(feature = "day04")]
+    solution!(4, 1, day_04, input_generator, part1),
+    #[cfg(feature = "day04")]
+    solution!(4, 2, day_04, input_generator, part2),
+    #[cfg(feature = "day05")]
+    solution!(5, 1, day_05, input_generator, part1),
+    #[cfg(feature = "day05")]
+    solution!(5, 2, day_05, input_generator, part2),
+    #[cfg(feature = "day06")]
+    solution!(6, 1, day_06, input_generator, part1),
+    #[cfg(feature = "day06")]
+    solution!(6, 2, day_06, input_generator, part2),
+    #[cfg(feature = "day07")]
+    solution!(7, 1, day_07, input_generator, part1, read_input),
+    #[cfg(feature = "day07")]
+    solution!(7, 2, day_07, input_generator, part2, read_input),
+    #[cfg(feature = "day08")]
+    solution!(8, 1, day_08, input_generator, part1),
+    #[cfg(feature = "day08")]
+    solution!(8, 2, day_08, input_generator, part2),
+    #[cfg(feature = "day09")]
+    solution!(9, 1, day_09, input_generator, part1),
+    #[cfg(feature = "day09")]
+    solution!(9, 2, day_09, input_generator, part2),
+    #[cfg(feature = "day10")]
+    solution!(10, 1, day_10, input_generator, part1),
+    #[cfg(feature = "day10")]
+    solution!(10, 2, day_10, input_generator, part2),
+    #[cfg(feature = "day11")]
+    solution!(11, 1, day_11, input_generator, part1),
+    #[cfg(feature = "day11")]
+    solution!(11, 2, day_11, input_generator, part2),
+    #[cfg(feature = "day12")]
+    solution!(12, 1, day_12, input_generator, part1),
+    #[cfg(feature = "day12")]
+    solution!(12, 2, day_12, input_generator, part2),
+    #[cfg(feature = "day13")]
+    solution!(13, 1, day_13, input_generator, part1),
+    #[cfg(feature = "day13")]
+    solution!(13, 2, day_13, input_generator, part2),
+    #[cfg(feature = "day14")]
+    solution!(
+        14,
+        1,
+        day_14,
+        input_generator,
+        part1,
+        example = example_generator
+    ),
+    #[cfg(feature = "day14")]
+    solution!(
+        14,
+        2,
+        day_14,
+        input_generator,
+        part2,
+        example = example_generator
+    ),
+    #[cfg(feature = "day15")]
+    solution!(15, 1, day_15, input_generator, part1),
+    #[cfg(feature = "day15")]
+    solution!(15, 2, day_15, input_generator, part2),
+    #[cfg(feature = "day16")]
+    solution!(16, 1, day_16, input_generator, part1),
+    #[cfg(feature = "day16")]
+    solution!(16, 2, day_16, input_generator, part2),
+];
+
+/// The registered solutions for `day`, optionally narrowed to a single `part`.
+pub fn find(day: u8, part: Option<u8>) -> Vec<Solution> {
+    SOLUTIONS
+        .iter()
+        .filter(|s| s.day == day && part.is_none_or(|p| s.part == p))
+        .cloned()
+        .collect()
+}
+
+/// Whether each day's cargo feature is enabled, indexed by day - 1.
+const COMPILED: [bool; 25] = [
+    cfg!(feature = "day01"),
+    cfg!(feature = "day02"),
+    cfg!(feature = "day03"),
+    cfg!(feature = "day04"),
+    cfg!(feature = "day05"),
+    cfg!(feature = "day06"),
+    cfg!(feature = "day07"),
+    cfg!(feature = "day08"),
+    cfg!(feature = "day09"),
+    cfg!(feature = "day10"),
+    cfg!(feature = "day11"),
+    cfg!(feature = "day12"),
+    cfg!(feature = "day13"),
+    cfg!(feature = "day14"),
+    cfg!(feature = "day15"),
+    cfg!(feature = "day16"),
+    cfg!(feature = "day17"),
+    cfg!(feature = "day18"),
+    cfg!(feature = "day19"),
+    cfg!(feature = "day20"),
+    cfg!(feature = "day21"),
+    cfg!(feature = "day22"),
+    cfg!(feature = "day23"),
+    cfg!(feature = "day24"),
+    cfg!(feature = "day25"),
+];
+
+/// Whether `day` was compiled in, by its `dayNN` cargo feature.
+pub fn compiled(day: u8) -> bool {
+    (1..=25).contains(&day) && COMPILED[usize::from(day) - 1]
+}
+
+/// The distinct days that have at least one registered solution.
+pub fn days() -> Vec<u8> {
+    let mut days: Vec<u8> = SOLUTIONS.iter().map(|s| s.day).collect();
+    days.dedup();
+    days
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "day03")]
+    fn test_find() {
+        let found = find(3, None);
+        assert_eq!(found.len(), 2);
+        assert!(found.iter().all(|s| s.day == 3));
+
+        let found = find(3, Some(2));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].part, 2);
+
+        assert!(find(25, None).is_empty());
+    }
+
+    #[test]
+    fn test_days() {
+        // no upper bound on the days written so far, so that adding one doesn't touch this test
+        let days = days();
+        assert!(
+            days.windows(2).all(|w| w[0] < w[1]),
+            "{days:?} out of order"
+        );
+        let registered: Vec<u8> = (1..=25)
+            .filter(|&day| compiled(day) && !find(day, None).is_empty())
+            .collect();
+        assert_eq!(days, registered);
+    }
+
+    #[test]
+    fn test_compiled() {
+        assert_eq!(compiled(6), cfg!(feature = "day06"));
+        assert!(!compiled(0));
+        assert!(!compiled(26));
+    }
+
+    #[test]
+    #[cfg(feature = "day09")]
+    fn test_run() {
+        let run = find(9, Some(1))[0].run("2333133121414131402\n").unwrap();
+        assert_eq!(run.answer, "1928");
+
+        let err = find(9, Some(1))[0].run("23x").unwrap_err();
+        assert_eq!(err, ParseError::new(1, 3, "x", "expected a digit"));
+    }
+
+    #[test]
+    fn test_run_reader() {
+        SOLUTIONS.iter().for_each(|s| {
+            let run = s.run_reader(&mut s.example.as_bytes()).unwrap();
+            assert_eq!(run.answer, s.run(s.example).unwrap().answer);
+        });
+    }
+
+    #[test]
+    #[cfg(all(feature = "day01", feature = "day07", feature = "day09"))]
+    fn test_run_reader_errors() {
+        assert!(find(7, Some(2))[0].streams());
+        assert!(!find(9, Some(1))[0].streams());
+
+        let err = find(9, Some(1))[0].run_reader(&mut "23x".as_bytes());
+        assert!(matches!(err, Err(ReadError::Parse(_))));
+        let err = find(1, Some(1))[0].run_reader(&mut &b"3 4\n\xff"[..]);
+        assert!(matches!(err, Err(ReadError::Io(_))));
+    }
+
+    #[test]
+    fn test_examples() {
+        SOLUTIONS.iter().for_each(|s| {
+            let solver = s.prepare_example().unwrap();
+            assert!(!solver().is_empty());
+        });
+    }
+
+    #[test]
+    #[cfg(feature = "day14")]
+    fn test_run_example() {
+        // the example's robots are in a smaller lobby than the real input's
+        let solution = find(14, Some(1))[0];
+        assert_eq!(solution.run_example().unwrap().answer, "12");
+        assert_eq!(solution.run(solution.example).unwrap().answer, "21");
+    }
+}
@@ -0,0 +1,83 @@
+use aoc2024::solutions::{self, SOLUTIONS};
+use std::fs;
+use std::path::Path;
+use toml::{Table, Value};
+
+fn answers() -> Table {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("answers.toml");
+    let contents = fs::read_to_string(&path).unwrap();
+    contents.parse().unwrap()
+}
+
+fn recorded(answers: &Table, day: u8, part: u8) -> Option<String> {
+    let value = answers
+        .get(&format!("day{day}"))?
+        .get(format!("part{part}"))?;
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Integer(n) => Some(n.to_string()),
+        other => panic!("day{day}.part{part} must be a string or integer, found {other}"),
+    }
+}
+
+#[test]
+fn test_recorded_answers() {
+    let answers = answers();
+    let input_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("input/2024");
+    let mut failures = vec![];
+    let mut compared = 0;
+
+    for solution in SOLUTIONS {
+        let (day, part) = (solution.day, solution.part);
+
+        let Ok(input) = fs::read_to_string(input_dir.join(format!("day{day}.txt"))) else {
+            eprintln!("day {day} part {part}: skipped, no input file");
+            continue;
+        };
+        // an input with nothing to compare against would pass whatever the solver returns
+        let Some(expected) = recorded(&answers, day, part) else {
+            failures.push(format!(
+                "day {day} part {part}: input present but no answer recorded in answers.toml"
+            ));
+            continue;
+        };
+        compared += 1;
+
+        match solution.run(&input) {
+            Ok(run) if run.answer == expected => {}
+            Ok(run) => failures.push(format!(
+                "day {day} part {part}: expected {expected}, got {}",
+                run.answer
+            )),
+            Err(e) => failures.push(format!("day {day} part {part}: {e}")),
+        }
+    }
+
+    if compared == 0 {
+        eprintln!("no recorded answers were compared, is the input submodule checked out?");
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
+
+#[test]
+fn test_answers_reference_registered_parts() {
+    let answers = answers();
+
+    for (day, parts) in &answers {
+        let number = day.strip_prefix("day").and_then(|n| n.parse().ok());
+        // answers for days left out of this build have nothing to reference
+        if number.is_some_and(|n| !solutions::compiled(n)) {
+            continue;
+        }
+        let parts = parts.as_table().unwrap();
+        for part in parts.keys() {
+            let registered = SOLUTIONS
+                .iter()
+                .any(|s| format!("day{}", s.day) == *day && format!("part{}", s.part) == *part);
+            assert!(
+                registered,
+                "answers.toml has {day}.{part} but no such solution"
+            );
+        }
+    }
+}
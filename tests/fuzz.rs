@@ -0,0 +1,210 @@
+//! A libFuzzer-free fuzz loop: every registered generator/solver pair is fed mutated copies of
+//! its day's example and synthetic inputs, plus arbitrary strings, and must return `Ok` or `Err`
+//! without panicking.
+//!
+//! Each part runs `FUZZ_ITERATIONS` cases (default 200, less for `SLOW_PARTS`) from seed `FUZZ_SEED` (default 0), so a
+//! run covers the same inputs on any machine. Setting `FUZZ_SECONDS` also stops each part after
+//! that long, for longer runs like
+//! `FUZZ_ITERATIONS=100000 FUZZ_SECONDS=60 cargo test --release --test fuzz`.
+
+use aoc2024::gen;
+use aoc2024::solutions::{Solution, SOLUTIONS};
+use arbitrary::{Arbitrary, Unstructured};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::env;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+/// Parts that are slow on every input however small, with the share of the iterations they run:
+/// day 11's 75 blinks take most of a second on each case in a debug build.
+const SLOW_PARTS: &[(u8, u8, u64)] = &[(11, 2, 10)];
+
+/// Fragments that are meaningful to at least one day's format.
+const TOKENS: &[&str] = &[
+    "0",
+    "1",
+    "9",
+    "-1",
+    " ",
+    "   ",
+    "\n",
+    "\n\n",
+    "\r\n",
+    "\u{feff}",
+    ",",
+    "|",
+    ": ",
+    ".",
+    "#",
+    "^",
+    "X",
+    "MAS",
+    "mul(",
+    ")",
+    "do()",
+    "don't()",
+    "Button A: X+",
+    ", Y+",
+    "Prize: X=",
+    "p=",
+    " v=",
+    "@",
+    "O",
+    "<>^v",
+    "S",
+    "E",
+    "4294967296",
+    "18446744073709551615",
+    "99999999999999999999",
+];
+
+#[derive(Arbitrary, Debug)]
+enum Mutation {
+    Insert(usize, char),
+    InsertToken(usize, u8),
+    Replace(usize, char),
+    Delete(usize, u8),
+    DuplicateLine(usize),
+    RemoveLine(usize),
+    SwapLines(usize, usize),
+    Truncate(usize),
+}
+
+fn boundary(input: &str, pos: usize) -> usize {
+    let mut pos = pos % (input.len() + 1);
+    while !input.is_char_boundary(pos) {
+        pos -= 1;
+    }
+    pos
+}
+
+impl Mutation {
+    fn apply(&self, input: &mut String) {
+        let mut lines: Vec<String> = input.split('\n').map(String::from).collect();
+        let line = |i: usize| i % lines.len();
+
+        match *self {
+            Mutation::Insert(pos, c) => input.insert(boundary(input, pos), c),
+            Mutation::InsertToken(pos, token) => {
+                input.insert_str(boundary(input, pos), TOKENS[token as usize % TOKENS.len()])
+            }
+            Mutation::Replace(pos, c) => {
+                let start = boundary(input, pos);
+                if let Some(old) = input[start..].chars().next() {
+                    input.replace_range(start..start + old.len_utf8(), &c.to_string());
+                }
+            }
+            Mutation::Delete(pos, len) => {
+                let start = boundary(input, pos);
+                let end = boundary(input, start + len as usize % 8).max(start);
+                input.replace_range(start..end, "");
+            }
+            Mutation::DuplicateLine(i) => {
+                let i = line(i);
+                lines.insert(i, lines[i].clone());
+                *input = lines.join("\n");
+            }
+            Mutation::RemoveLine(i) => {
+                lines.remove(line(i));
+                *input = lines.join("\n");
+            }
+            Mutation::SwapLines(i, j) => {
+                let (i, j) = (line(i), line(j));
+                lines.swap(i, j);
+                *input = lines.join("\n");
+            }
+            Mutation::Truncate(pos) => input.truncate(boundary(input, pos)),
+        }
+    }
+}
+
+#[derive(Arbitrary, Debug)]
+enum Case {
+    /// A corpus entry with a few mutations applied.
+    Mutated(u8, Vec<Mutation>),
+    Raw(String),
+}
+
+/// The example, and a small synthetic input when the day has a generator.
+fn corpus(solution: &Solution) -> Vec<String> {
+    let mut corpus = vec![solution.example.to_string()];
+    if let Some(generator) = gen::find(solution.day) {
+        corpus.extend((0..3).map(|seed| generator.generate(seed, 6)));
+    }
+    corpus
+}
+
+fn case_input(case: &Case, corpus: &[String]) -> String {
+    match case {
+        Case::Mutated(entry, mutations) => {
+            let mut input = corpus[*entry as usize % corpus.len()].clone();
+            mutations.iter().take(8).for_each(|m| m.apply(&mut input));
+            input
+        }
+        Case::Raw(input) => input.clone(),
+    }
+}
+
+fn env_or(name: &str, default: u64) -> u64 {
+    env::var(name)
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(default)
+}
+
+#[test]
+fn test_fuzz_solutions() {
+    let iterations = env_or("FUZZ_ITERATIONS", 200);
+    let first_seed = env_or("FUZZ_SEED", 0);
+    // a time limit makes coverage depend on the machine, so there is none unless asked for
+    let budget = env::var("FUZZ_SECONDS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .map(Duration::from_secs);
+    let mut failures = vec![];
+
+    // panics are expected to be caught and reported below, not printed as they happen
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    for solution in SOLUTIONS {
+        let corpus = corpus(solution);
+        let share = SLOW_PARTS
+            .iter()
+            .find(|&&(day, part, _)| (day, part) == (solution.day, solution.part))
+            .map_or(1, |&(_, _, share)| share);
+        let start = Instant::now();
+        for seed in first_seed..first_seed + (iterations / share).max(1) {
+            if budget.is_some_and(|budget| start.elapsed() > budget) {
+                break;
+            }
+
+            let mut bytes = vec![0u8; 256];
+            StdRng::seed_from_u64(seed).fill_bytes(&mut bytes);
+            let Ok(case) = Case::arbitrary(&mut Unstructured::new(&bytes)) else {
+                continue;
+            };
+            let input = case_input(&case, &corpus);
+
+            if let Err(e) = panic::catch_unwind(AssertUnwindSafe(|| {
+                solution.run(&input).map(|run| run.answer)
+            })) {
+                let message = e
+                    .downcast_ref::<String>()
+                    .cloned()
+                    .or_else(|| e.downcast_ref::<&str>().map(|s| s.to_string()))
+                    .unwrap_or_default();
+                failures.push(format!(
+                    "day {} part {} seed {seed}: {message}\n    input: {input:?}",
+                    solution.day, solution.part
+                ));
+                // one failing case per part is enough to go on
+                break;
+            }
+        }
+    }
+
+    panic::set_hook(hook);
+    assert!(failures.is_empty(), "panics:\n{}", failures.join("\n"));
+}